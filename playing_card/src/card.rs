@@ -54,6 +54,22 @@ impl NonJokerCard {
     pub fn rank(self) -> CardRank {
         self.rank
     }
+
+    /// Short notation of the rank and the suit, e.g. "Ah" or "Td".
+    pub fn code(self) -> String {
+        format!("{}{}", self.rank.to_char(), self.suit.to_letter())
+    }
+
+    /// Parses a short notation given by code().
+    pub fn from_code(code: &str) -> Option<Self> {
+        let mut chars = code.chars();
+        let rank = CardRank::from_char(chars.next()?)?;
+        let suit = Suit::from_letter(chars.next()?)?;
+        if chars.next().is_some() {
+            return None;
+        }
+        Some(Self { suit, rank })
+    }
 }
 
 impl fmt::Display for NonJokerCard {
@@ -80,6 +96,16 @@ mod test {
         assert_eq!(card_count, unique_set.len());
     }
 
+    #[test]
+    fn code_is_parsed_back_to_card() {
+        for card in NonJokerCard::all() {
+            assert_eq!(Some(card), NonJokerCard::from_code(&card.code()));
+        }
+        assert_eq!("Ah", NonJokerCard::new(Suit::Heart, CardRank::Ace).code());
+        assert_eq!(None, NonJokerCard::from_code("Ahh"));
+        assert_eq!(None, NonJokerCard::from_code("hA"));
+    }
+
     #[test]
     fn all_cards_with_one_joker_are_53_cards() {
         assert_eq!(53, Card::all_cards_with_one_joker().len());
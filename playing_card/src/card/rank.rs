@@ -1,5 +1,7 @@
-use std::fmt;
+use crate::error::CardError;
 use std::char;
+use std::convert::TryFrom;
+use std::fmt;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub enum CardRank {
@@ -14,14 +16,19 @@ pub enum CardRank {
 pub struct CardRankNumber { value: u8 }
 
 impl CardRank {
+    /// Panics if the value is not from 1 to 13. Use try_new for values from outside.
     pub fn new(value: u8) -> Self {
+        Self::try_new(value).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(value: u8) -> Result<Self, CardError> {
         match value {
-            1 => CardRank::Ace,
-            13 => CardRank::King,
-            12 => CardRank::Queen,
-            11 => CardRank::Jack,
-            2 ..= 10 => CardRank::Number(CardRankNumber {value}),
-            x => panic!(format!("Argument {} is out of range", x).to_string()),
+            1 => Ok(CardRank::Ace),
+            13 => Ok(CardRank::King),
+            12 => Ok(CardRank::Queen),
+            11 => Ok(CardRank::Jack),
+            2 ..= 10 => Ok(CardRank::Number(CardRankNumber {value})),
+            x => Err(CardError::RankOutOfRange(x)),
         }
     }
 
@@ -64,18 +71,17 @@ impl CardRank {
         }
     }
 
+    /// Inverse of to_char.
+    pub fn from_char(c: char) -> Option<Self> {
+        Self::all().iter().find(|r| r.to_char() == c).copied()
+    }
+
     pub fn is_ace(self) -> bool {
-        match self {
-            CardRank::Ace => true,
-            _ => false,
-        }
+        matches!(self, CardRank::Ace)
     }
 
     pub fn is_picture_card(self) -> bool {
-        match self {
-            Self::King | Self::Queen | Self::Jack => true,
-            _ => false,
-        }
+        matches!(self, Self::King | Self::Queen | Self::Jack)
     }
 }
 
@@ -88,6 +94,14 @@ impl fmt::Display for CardRank {
     }
 }
 
+impl TryFrom<u8> for CardRank {
+    type Error = CardError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -140,6 +154,19 @@ mod test {
         CardRank::new(14);
     }
 
+    #[test]
+    fn rank_out_of_range_is_an_error() {
+        assert_eq!(Err(CardError::RankOutOfRange(14)), CardRank::try_new(14));
+        assert_eq!(Ok(CardRank::Jack), CardRank::try_from(11));
+    }
+
+    #[test]
+    fn rank_is_parsed_from_char() {
+        assert_eq!(Some(CardRank::new(10)), CardRank::from_char('T'));
+        assert_eq!(Some(CardRank::Ace), CardRank::from_char('A'));
+        assert_eq!(None, CardRank::from_char('1'));
+    }
+
     #[test]
     fn ace_is_1_in_int() {
         assert_eq!(1, CardRank::Ace.to_int());
@@ -37,6 +37,20 @@ impl Suit {
             Suit::Heart => '\u{2665}',
         }
     }
+
+    /// Lowercase initial used in short card notations like "Ah".
+    pub fn to_letter(self) -> char {
+        match self {
+            Suit::Club => 'c',
+            Suit::Spade => 's',
+            Suit::Diamond => 'd',
+            Suit::Heart => 'h',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        Self::all().iter().find(|s| s.to_letter() == letter).copied()
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +76,12 @@ mod test {
     fn heart_is_red() {
         assert_eq!(Suit::Heart.color(), SuitColor::Red)
     }
+
+    #[test]
+    fn suit_is_parsed_from_letter() {
+        for suit in Suit::all().iter() {
+            assert_eq!(Some(*suit), Suit::from_letter(suit.to_letter()));
+        }
+        assert_eq!(None, Suit::from_letter('x'));
+    }
 }
\ No newline at end of file
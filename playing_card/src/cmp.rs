@@ -1,6 +1,7 @@
 use super::card::CardRank;
 use super::card::NonJokerCard;
 use super::card::Suit;
+use super::error::CardError;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -26,12 +27,12 @@ impl CardComparer {
 
     /// Create new object with specific suit order.
     /// Give suit_order in descending order. The former suit is greater.
-    pub fn new_with_suit_order(highest_rank: CardRank, suit_order: [Suit;4]) -> Result<Self, String> {
+    pub fn new_with_suit_order(highest_rank: CardRank, suit_order: [Suit;4]) -> Result<Self, CardError> {
         let mut suit_strengths = HashMap::new();
         for i in 0 ..= 3 {
-            let old_value = suit_strengths.insert(suit_order[usize::from(i)], 4 - i);
-            if let Some(x) = old_value {
-                return Err(format!("{} is duplicated in suit order", x));
+            let suit = suit_order[usize::from(i)];
+            if suit_strengths.insert(suit, 4 - i).is_some() {
+                return Err(CardError::DuplicateSuit(suit));
             }
         }
         Ok(Self {highest_rank, suit_strengths})
@@ -172,7 +173,7 @@ mod test {
             Suit::Club,
         ];
         target.sort_by(|a, b| comparer.cmp_suits(*a, *b).reverse()); // must reverse because given order is descending
-        let expected: Vec<Suit> = suits_order.iter().map(|s| *s).collect();
+        let expected: Vec<Suit> = suits_order.to_vec();
         assert_eq!(expected, target);
     }
 
@@ -193,7 +194,7 @@ mod test {
             Suit::Club,
         ];
         target.sort_by(|a, b| comparer.cmp_suits(*a, *b).reverse()); // must reverse because given order is descending
-        let expected: Vec<Suit> = suits_order.iter().map(|s| *s).collect();
+        let expected: Vec<Suit> = suits_order.to_vec();
         assert_eq!(expected, target);
     }
 
@@ -207,11 +208,24 @@ mod test {
             Suit::Diamond,
         ];
         let result = CardComparer::new_with_suit_order(CardRank::King, suits_order);
-        if let Ok(_) = result {
+        if result.is_ok() {
             panic!("Error is expected but not occurred")
         }
     }
 
+    #[test]
+    fn duplicate_suit_is_named_in_error() {
+        let suits_order = [
+            Suit::Heart,
+            Suit::Spade,
+            Suit::Heart,
+            Suit::Diamond,
+        ];
+        let error = CardComparer::new_with_suit_order(CardRank::King, suits_order).unwrap_err();
+        assert_eq!(CardError::DuplicateSuit(Suit::Heart), error);
+        assert_eq!("Heart is duplicated in suit order", error.to_string());
+    }
+
     #[test]
     fn cards_are_compared_by_rank_at_first() {
         let suits_order = [
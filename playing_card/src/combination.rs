@@ -0,0 +1,230 @@
+use std::borrow::Cow;
+
+/// Number of ways to choose k items out of n items.
+pub fn combination_count(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    (0 .. k).fold(1, |count, i| count * (n - i) / (i + 1))
+}
+
+/// Iterator over all combinations of k items in lexicographic order of their positions,
+/// e.g. [a, b], [a, c], [b, c] for 2 of [a, b, c].
+/// Items in each combination keep the order of the given items.
+///
+/// It can be split into iterators over consecutive parts, e.g. to enumerate them on threads.
+/// Items can be borrowed, and next_into() or next_array() gives combinations without allocating.
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T: Clone> {
+    items: Cow<'a, [T]>,
+    /// Number of items in each combination.
+    k: usize,
+    /// Positions of the items in the next combination.
+    indices: Vec<usize>,
+    /// Lexicographic number of the next combination.
+    position: usize,
+    end: usize,
+}
+
+impl<'a, T: Clone> Combinations<'a, T> {
+    pub fn new(items: Vec<T>, k: usize) -> Self {
+        let end = combination_count(items.len(), k);
+        Self::starting_at(Cow::Owned(items), k, 0, end)
+    }
+
+    /// Combinations of borrowed items, which are not copied even when it is split.
+    pub fn from_slice(items: &'a [T], k: usize) -> Self {
+        let end = combination_count(items.len(), k);
+        Self::starting_at(Cow::Borrowed(items), k, 0, end)
+    }
+
+    fn starting_at(items: Cow<'a, [T]>, k: usize, position: usize, end: usize) -> Self {
+        let indices = if position < end { nth_combination(items.len(), k, position) } else { Vec::new() };
+        Self { items, k, indices, position, end }
+    }
+
+    /// Splits into an iterator over the first given number of combinations and one over the rest.
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let mid = (self.position + mid).min(self.end);
+        let rest = Self::starting_at(self.items.clone(), self.k, mid, self.end);
+        (Self { end: mid, ..self }, rest)
+    }
+
+    /// Splits into given number of iterators whose lengths differ by 1 at most.
+    pub fn split(self, parts: usize) -> Vec<Self> {
+        assert!(parts > 0, "Combinations cannot be split into 0 parts");
+        let mut split = Vec::with_capacity(parts);
+        let mut rest = self;
+        for i in 0 .. parts - 1 {
+            let size = rest.len().div_ceil(parts - i);
+            let (first, second) = rest.split_at(size);
+            split.push(first);
+            rest = second;
+        }
+        split.push(rest);
+        split
+    }
+
+    /// Writes the next combination to the buffer of k items and returns true, or returns false if none is left.
+    pub fn next_into(&mut self, buffer: &mut [T]) -> bool {
+        assert_eq!(self.k, buffer.len(), "Buffer must have as many items as a combination");
+        if self.position >= self.end {
+            return false;
+        }
+        for (b, i) in buffer.iter_mut().zip(self.indices.iter()) {
+            b.clone_from(&self.items[*i]);
+        }
+        self.step();
+        true
+    }
+
+    /// The next combination of K items, which must be k.
+    pub fn next_array<const K: usize>(&mut self) -> Option<[T; K]> {
+        assert_eq!(self.k, K, "Array must have as many items as a combination");
+        if self.position >= self.end {
+            return None;
+        }
+        let combination = std::array::from_fn(|j| self.items[self.indices[j]].clone());
+        self.step();
+        Some(combination)
+    }
+
+    fn step(&mut self) {
+        self.position += 1;
+        if self.position < self.end {
+            advance(&mut self.indices, self.items.len());
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.end {
+            return None;
+        }
+        // any k items, overwritten with the next combination
+        let mut combination = self.items[.. self.k].to_vec();
+        self.next_into(&mut combination);
+        Some(combination)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.position;
+        (len, Some(len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for Combinations<'_, T> {}
+
+/// Moves indices to the next combination. The last combination must not be given.
+fn advance(indices: &mut [usize], n: usize) {
+    let k = indices.len();
+    let mut i = k;
+    while indices[i - 1] == n - k + i - 1 {
+        i -= 1;
+    }
+    indices[i - 1] += 1;
+    for j in i .. k {
+        indices[j] = indices[j - 1] + 1;
+    }
+}
+
+/// Positions of the combination with given lexicographic number.
+fn nth_combination(n: usize, k: usize, mut position: usize) -> Vec<usize> {
+    let mut indices = Vec::with_capacity(k);
+    let mut next = 0;
+    for i in 0 .. k {
+        loop {
+            // combinations starting with `next` at this place
+            let count = combination_count(n - next - 1, k - i - 1);
+            if position < count {
+                break;
+            }
+            position -= count;
+            next += 1;
+        }
+        indices.push(next);
+        next += 1;
+    }
+    indices
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn combination_count_is_binomial_coefficient() {
+        assert_eq!(2598960, combination_count(52, 5));
+        assert_eq!(1, combination_count(5, 0));
+        assert_eq!(1, combination_count(5, 5));
+        assert_eq!(0, combination_count(2, 3));
+    }
+
+    #[test]
+    fn combinations_are_in_lexicographic_order() {
+        let all: Vec<Vec<u8>> = Combinations::new(vec![1, 2, 3, 4], 2).collect();
+        assert_eq!(vec![vec![1, 2], vec![1, 3], vec![1, 4], vec![2, 3], vec![2, 4], vec![3, 4]], all);
+    }
+
+    #[test]
+    fn combination_of_no_item_is_empty() {
+        let all: Vec<Vec<u8>> = Combinations::new(vec![1, 2], 0).collect();
+        assert_eq!(vec![Vec::<u8>::new()], all);
+        assert_eq!(0, Combinations::new(vec![1, 2], 3).count());
+    }
+
+    #[test]
+    fn combination_of_more_items_than_given_is_not_taken() {
+        assert_eq!(None, Combinations::new(vec![1, 2], 3).next_array::<3>());
+        assert!(!Combinations::new(vec![1, 2], 3).next_into(&mut [0; 3]));
+        let (first, mut rest) = Combinations::new(vec![1, 2, 3], 2).split_at(3);
+        assert_eq!(3, first.count());
+        assert_eq!(None, rest.next_array::<2>());
+    }
+
+    #[test]
+    fn length_is_exact_while_iterating() {
+        let mut combinations = Combinations::new((0 .. 10).collect::<Vec<u8>>(), 3);
+        assert_eq!(120, combinations.len());
+        combinations.next();
+        assert_eq!(119, combinations.len());
+    }
+
+    #[test]
+    fn combinations_can_be_written_to_a_buffer() {
+        let items = [1, 2, 3, 4];
+        let mut combinations = Combinations::from_slice(&items, 3);
+        let mut buffer = [0; 3];
+        let mut all = Vec::new();
+        while combinations.next_into(&mut buffer) {
+            all.push(buffer);
+        }
+        assert_eq!(vec![[1, 2, 3], [1, 2, 4], [1, 3, 4], [2, 3, 4]], all);
+        assert!(!combinations.next_into(&mut buffer));
+    }
+
+    #[test]
+    fn combinations_can_be_taken_as_arrays() {
+        let mut combinations = Combinations::new(vec![1, 2, 3], 2);
+        assert_eq!(Some([1, 2]), combinations.next_array());
+        assert_eq!(vec![1, 3], combinations.next().unwrap());
+        assert_eq!(Some([2, 3]), combinations.next_array());
+        assert_eq!(None, combinations.next_array::<2>());
+    }
+
+    #[test]
+    fn split_parts_make_up_all_combinations() {
+        let all: Vec<Vec<u8>> = Combinations::new((0 .. 7).collect(), 3).collect();
+        let mut consumed = Combinations::new((0 .. 7).collect::<Vec<u8>>(), 3);
+        consumed.next();
+        let parts = consumed.split(4);
+        assert_eq!(vec![9, 9, 8, 8], parts.iter().map(|p| p.len()).collect::<Vec<usize>>());
+        let joined: Vec<Vec<u8>> = parts.into_iter().flatten().collect();
+        assert_eq!(all[1 ..].to_vec(), joined);
+    }
+}
@@ -1,3 +1,8 @@
+use crate::card::{ Card, CardRank, NonJokerCard, Suit };
+use crate::combination::Combinations;
+use crate::error::CardError;
+use std::collections::HashSet;
+use rand::Rng;
 use rand::seq::SliceRandom;
 
 #[derive(Debug, Clone)]
@@ -20,7 +25,14 @@ impl<T> Deck<T> {
     pub fn shuffle(&mut self)
     {
         let mut rng = rand::thread_rng();
-        self.card_vec.shuffle(&mut rng);
+        self.shuffle_with(&mut rng);
+    }
+
+    /// Randomize card order with given random number generator,
+    /// e.g. a seeded one to reproduce the same order.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R)
+    {
+        self.card_vec.shuffle(rng);
     }
 
     /// Remove a card from the deck top and return it.
@@ -49,6 +61,16 @@ impl<T> Deck<T> {
         dealt
     }
 
+    /// Remove exactly given number of cards from the deck top and return them.
+    /// Returns an error without removing any cards if the deck does not have enough cards.
+    pub fn try_deal_many(&mut self, size: usize) -> Result<Vec<T>, CardError>
+    {
+        if self.card_vec.len() < size {
+            return Err(CardError::DeckExhausted);
+        }
+        Ok(self.card_vec.drain(0 .. size).collect())
+    }
+
     /// Remove all cards that match given condition and return them.
     pub fn search<F: Fn(&T) -> bool>(&mut self, condition: F) -> Vec<T>
     {
@@ -66,6 +88,111 @@ impl<T> Deck<T> {
     }
 }
 
+impl<T: Clone> Deck<T> {
+    /// All combinations of given number of cards left in the deck, without dealing them.
+    pub fn combinations(&self, size: usize) -> Combinations<'_, T>
+    {
+        Combinations::from_slice(&self.card_vec, size)
+    }
+}
+
+/// Builder of decks of other cards than the standard 52 cards,
+/// e.g. a shoe of several decks, a stripped deck or a deck of some suits and ranks.
+///
+/// Cards of a deck are in the given order of suits and ranks, and copies follow one after another.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DeckBuilder {
+    cards: Vec<NonJokerCard>,
+    copies: usize,
+}
+
+impl Default for DeckBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeckBuilder {
+    /// The standard 52 cards.
+    pub fn new() -> Self {
+        Self::with_cards(&NonJokerCard::all())
+    }
+
+    /// Cards of the ranks in all suits.
+    pub fn with_ranks(ranks: &[CardRank]) -> Self {
+        Self::with_suits_and_ranks(&Suit::all(), ranks)
+    }
+
+    /// Cards of the ranks in each of the suits.
+    pub fn with_suits_and_ranks(suits: &[Suit], ranks: &[CardRank]) -> Self {
+        let cards: Vec<NonJokerCard> = suits.iter()
+            .flat_map(|s| ranks.iter().map(move |r| NonJokerCard::new(*s, *r)))
+            .collect();
+        Self::with_cards(&cards)
+    }
+
+    /// A deck of given cards.
+    pub fn with_cards(cards: &[NonJokerCard]) -> Self {
+        Self { cards: cards.to_vec(), copies: 1 }
+    }
+
+    /// 36 cards from Six to Ace.
+    pub fn short_deck() -> Self {
+        Self::with_ranks(&ranks_down_to(6))
+    }
+
+    /// 32 cards from Seven to Ace.
+    pub fn piquet() -> Self {
+        Self::with_ranks(&ranks_down_to(7))
+    }
+
+    /// 24 cards from Nine to Ace.
+    pub fn euchre() -> Self {
+        Self::with_ranks(&ranks_down_to(9))
+    }
+
+    /// 48 cards of two copies of cards from Nine to Ace.
+    pub fn pinochle() -> Self {
+        Self::euchre().copies(2)
+    }
+
+    /// Combines given number of copies of the deck, e.g. into a shoe.
+    pub fn copies(mut self, copies: usize) -> Self {
+        self.copies = copies;
+        self
+    }
+
+    /// All cards of the deck in order.
+    /// Returns an error if a card appears twice in a copy, e.g. because a suit or a rank is given twice,
+    /// or if 0 copies are given.
+    pub fn cards(&self) -> Result<Vec<NonJokerCard>, CardError> {
+        if self.copies == 0 {
+            return Err(CardError::NoCopies);
+        }
+        let mut seen = HashSet::new();
+        if let Some(card) = self.cards.iter().find(|c| !seen.insert(**c)) {
+            return Err(CardError::DuplicateCard(*card));
+        }
+        Ok(self.cards.repeat(self.copies))
+    }
+
+    pub fn build(&self) -> Result<Deck<NonJokerCard>, CardError> {
+        Ok(Deck::new(self.cards()?))
+    }
+
+    /// Deck with given number of jokers after the other cards.
+    pub fn build_with_jokers(&self, jokers: usize) -> Result<Deck<Card>, CardError> {
+        let mut cards: Vec<Card> = self.cards()?.into_iter().map(Card::Other).collect();
+        cards.extend(std::iter::repeat_n(Card::Joker, jokers));
+        Ok(Deck::new(cards))
+    }
+}
+
+/// Ace and ranks from King down to given rank.
+fn ranks_down_to(lowest: u8) -> Vec<CardRank> {
+    CardRank::all().iter().copied().filter(|r| *r == CardRank::Ace || r.to_int() >= lowest).collect()
+}
+
 
 #[cfg(test)]
 mod test {
@@ -82,6 +209,26 @@ mod test {
         assert_ne!(items, deck.cards());
     }
 
+    #[test]
+    fn same_seed_gives_same_order() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        let items: Vec<u8> = (0 .. 52).collect();
+        let mut a = Deck::new(items.clone());
+        let mut b = Deck::new(items);
+        a.shuffle_with(&mut StdRng::seed_from_u64(42));
+        b.shuffle_with(&mut StdRng::seed_from_u64(42));
+        assert_eq!(a.cards(), b.cards());
+    }
+
+    #[test]
+    fn combinations_do_not_deal_cards() {
+        let deck = Deck::new(vec![10, 20, 30]);
+        let combinations: Vec<Vec<u8>> = deck.combinations(2).collect();
+        assert_eq!(vec![vec![10, 20], vec![10, 30], vec![20, 30]], combinations);
+        assert_eq!(3, deck.cards().len());
+    }
+
     #[test]
     fn deal_one_returns_first_item() {
         let items = vec![10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
@@ -147,6 +294,13 @@ mod test {
         assert_eq!(&vec![40, 50, 60, 70, 80, 90, 100], deck.cards());
     }
 
+    #[test]
+    fn try_deal_many_does_not_deal_when_deck_does_not_have_enough_cards() {
+        let mut deck = Deck::new(vec![90, 100]);
+        assert_eq!(Err(CardError::DeckExhausted), deck.try_deal_many(3));
+        assert_eq!(Ok(vec![90, 100]), deck.try_deal_many(2));
+    }
+
     #[test]
     fn search_returns_matched_cards() {
         let items = vec![10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
@@ -162,4 +316,72 @@ mod test {
         let _ = deck.search(|x| x % 30 == 0);
         assert_eq!(vec![10, 20, 40, 50, 70, 80, 100], deck.cards());
     }
-}
\ No newline at end of file
+
+    fn ranks_of(deck: &Deck<NonJokerCard>) -> HashSet<CardRank> {
+        deck.cards().iter().map(|c| c.rank()).collect()
+    }
+
+    #[test]
+    fn builder_makes_standard_deck_by_default() {
+        assert_eq!(NonJokerCard::all(), DeckBuilder::new().build().unwrap().cards());
+    }
+
+    #[test]
+    fn stripped_decks_have_aces_and_high_ranks() {
+        let short_deck = DeckBuilder::short_deck().build().unwrap();
+        assert_eq!(36, short_deck.cards().len());
+        assert!(ranks_of(&short_deck).contains(&CardRank::Ace));
+        assert!(!ranks_of(&short_deck).contains(&CardRank::new(5)));
+        assert_eq!(32, DeckBuilder::piquet().build().unwrap().cards().len());
+        assert_eq!(24, DeckBuilder::euchre().build().unwrap().cards().len());
+        assert_eq!(9, ranks_of(&DeckBuilder::short_deck().build().unwrap()).len());
+    }
+
+    #[test]
+    fn pinochle_has_two_of_each_card() {
+        let cards = DeckBuilder::pinochle().cards().unwrap();
+        assert_eq!(48, cards.len());
+        assert_eq!(24, cards.iter().collect::<HashSet<&NonJokerCard>>().len());
+    }
+
+    #[test]
+    fn shoe_repeats_deck() {
+        let cards = DeckBuilder::new().copies(6).cards().unwrap();
+        assert_eq!(312, cards.len());
+        assert_eq!(&cards[.. 52], &cards[52 .. 104]);
+    }
+
+    #[test]
+    fn custom_deck_has_given_suits_and_ranks() {
+        let cards = DeckBuilder::with_suits_and_ranks(&[Suit::Heart, Suit::Spade], &[CardRank::Ace, CardRank::King]).cards().unwrap();
+        let expected = vec![
+            NonJokerCard::new(Suit::Heart, CardRank::Ace),
+            NonJokerCard::new(Suit::Heart, CardRank::King),
+            NonJokerCard::new(Suit::Spade, CardRank::Ace),
+            NonJokerCard::new(Suit::Spade, CardRank::King),
+        ];
+        assert_eq!(expected, cards);
+    }
+
+    #[test]
+    fn duplicate_card_in_a_copy_is_an_error() {
+        let builder = DeckBuilder::with_ranks(&[CardRank::Ace, CardRank::new(2), CardRank::Ace]);
+        let error = builder.build().unwrap_err();
+        assert_eq!(CardError::DuplicateCard(NonJokerCard::new(Suit::all()[0], CardRank::Ace)), error);
+    }
+
+    #[test]
+    fn jokers_come_after_other_cards() {
+        let deck = DeckBuilder::euchre().build_with_jokers(2).unwrap();
+        assert_eq!(26, deck.cards().len());
+        assert_eq!(&[Card::Joker, Card::Joker], &deck.cards()[24 ..]);
+        assert_eq!(Card::all_cards_with_two_jokers(), DeckBuilder::new().build_with_jokers(2).unwrap().cards());
+    }
+
+    #[test]
+    fn deck_needs_a_copy() {
+        let builder = DeckBuilder::new().copies(0);
+        assert_eq!(Err(CardError::NoCopies), builder.cards());
+        assert_eq!(Err(CardError::NoCopies), builder.build().map(|d| d.cards().len()));
+    }
+}
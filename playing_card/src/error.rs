@@ -0,0 +1,30 @@
+use crate::card::{ NonJokerCard, Suit };
+use std::error;
+use std::fmt;
+
+/// Errors of invalid cards and decks.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CardError {
+    /// A rank number which is not from 1 to 13.
+    RankOutOfRange(u8),
+    DuplicateSuit(Suit),
+    DuplicateCard(NonJokerCard),
+    /// The deck does not have enough cards to deal.
+    DeckExhausted,
+    /// A deck is built of 0 copies of its cards.
+    NoCopies,
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RankOutOfRange(value) => write!(f, "Rank {} is out of range from 1 to 13", value),
+            Self::DuplicateSuit(suit) => write!(f, "{:?} is duplicated in suit order", suit),
+            Self::DuplicateCard(card) => write!(f, "{} is duplicated", card),
+            Self::DeckExhausted => write!(f, "Card deck has run out"),
+            Self::NoCopies => write!(f, "A deck needs 1 or more copies"),
+        }
+    }
+}
+
+impl error::Error for CardError {}
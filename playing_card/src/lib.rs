@@ -2,4 +2,9 @@ extern crate rand;
 
 pub mod deck;
 pub mod card;
-pub mod cmp;
\ No newline at end of file
+pub mod combination;
+pub mod cmp;
+pub mod error;
+
+#[cfg(feature = "serde")]
+mod serialization;
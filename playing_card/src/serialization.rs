@@ -0,0 +1,140 @@
+//! Serde support enabled by the "serde" feature.
+//! Cards are encoded in short notations such as "Ah", so that they are readable in JSON.
+
+use crate::card::{ Card, CardRank, NonJokerCard, Suit };
+use crate::deck::Deck;
+use serde::de::{ Deserialize, Deserializer, Error };
+use serde::ser::{ Serialize, Serializer };
+
+/// Deserializes a string and parses it, or fails with the name of the expected kind.
+fn parse_string<'de, D, T, F>(deserializer: D, kind: &str, parse: F) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: Fn(&str) -> Option<T>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse(&s).ok_or_else(|| D::Error::custom(format!("Invalid {}: {}", kind, s)))
+}
+
+/// Encoded as "s", "d", "h" or "c".
+impl Serialize for Suit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_letter().to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Suit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_string(deserializer, "suit", |s| {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Suit::from_letter(c),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Encoded as "A", "K", "Q", "J", "T" or "9" to "2".
+impl Serialize for CardRank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_char().to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for CardRank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_string(deserializer, "rank", |s| {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => CardRank::from_char(c),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Encoded as NonJokerCard::code(), e.g. "Ah".
+impl Serialize for NonJokerCard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for NonJokerCard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_string(deserializer, "card", NonJokerCard::from_code)
+    }
+}
+
+const JOKER: &str = "Joker";
+
+/// Encoded as "Joker" or the code of a non-joker card.
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Card::Joker => serializer.serialize_str(JOKER),
+            Card::Other(c) => c.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_string(deserializer, "card", |s| {
+            if s == JOKER { Some(Card::Joker) } else { NonJokerCard::from_code(s).map(Card::Other) }
+        })
+    }
+}
+
+/// Encoded as the list of cards left in the deck in order of dealing.
+impl<T: Serialize> Serialize for Deck<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.cards().serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Deck<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(Deck::new)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cards_are_encoded_in_short_notation() {
+        let card = NonJokerCard::new(Suit::Heart, CardRank::Ace);
+        assert_eq!("\"Ah\"", serde_json::to_string(&card).unwrap());
+        assert_eq!("\"T\"", serde_json::to_string(&CardRank::new(10)).unwrap());
+        assert_eq!("\"c\"", serde_json::to_string(&Suit::Club).unwrap());
+        assert_eq!("[\"Joker\",\"Ah\"]", serde_json::to_string(&[Card::Joker, Card::Other(card)]).unwrap());
+    }
+
+    #[test]
+    fn all_cards_are_decoded_back() {
+        let cards = Card::all_cards_with_one_joker();
+        let json = serde_json::to_string(&cards).unwrap();
+        assert_eq!(cards, serde_json::from_str::<Vec<Card>>(&json).unwrap());
+    }
+
+    #[test]
+    fn deck_keeps_order_of_cards() {
+        let mut deck = Deck::new(NonJokerCard::all());
+        deck.shuffle();
+        deck.deal_many(10);
+        let restored: Deck<NonJokerCard> = serde_json::from_str(&serde_json::to_string(&deck).unwrap()).unwrap();
+        assert_eq!(deck.cards(), restored.cards());
+    }
+
+    #[test]
+    fn invalid_cards_are_not_decoded() {
+        let error = serde_json::from_str::<NonJokerCard>("\"1h\"").unwrap_err();
+        assert!(error.to_string().contains("Invalid card: 1h"));
+        assert!(serde_json::from_str::<Suit>("\"hh\"").is_err());
+        assert!(serde_json::from_str::<CardRank>("\"\"").is_err());
+    }
+}
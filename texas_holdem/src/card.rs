@@ -1,19 +1,37 @@
 mod best_five_hand;
 mod board;
+mod board_texture;
+mod canonical;
+mod comparison;
+mod draw;
+mod hand_class;
 mod hand_value;
+mod made_hand;
+mod nuts;
 mod rank;
 mod starting_hand;
+mod strength;
 mod total_hand;
 
 pub use best_five_hand::BestFiveHand;
 pub use board::Board;
+pub use board_texture::{ BoardPairing, BoardTexture, SuitTexture, TextureChange, Wetness };
+pub use canonical::CanonicalForm;
+pub use comparison::{ Decider, HandComparison };
+pub use draw::{ Draw, DrawKind, Draws };
+pub use hand_class::HandClass;
 pub use hand_value::HandValue;
+pub use made_hand::{ KickerQuality, MadeHand, MadeHandCategory };
+pub use nuts::NutHand;
 pub use rank::HandRank;
-pub use starting_hand::StartingHand;
+pub use starting_hand::{ StartingHand, StartingHandCombos };
+pub use strength::DISTINCT_HAND_VALUE_COUNT;
 pub use total_hand::TotalHand;
 
+use crate::error::HoldemError;
 use playing_card::cmp::CardComparer;
 use playing_card::card::{ CardRank, NonJokerCard };
+use playing_card::error::CardError;
 use std::cmp::Ordering;
 
 fn get_poker_card_comparer() -> CardComparer {
@@ -28,4 +46,13 @@ pub fn cmp_card_ranks(a: CardRank, b: CardRank) -> Ordering {
 pub fn cmp_cards(a: NonJokerCard, b: NonJokerCard) -> Ordering {
     let comparer = get_poker_card_comparer();
     comparer.cmp_cards(a, b)
-}
\ No newline at end of file
+}
+/// Returns an error with the first card which appears twice.
+fn check_no_duplicates(cards: &[NonJokerCard]) -> Result<(), HoldemError> {
+    for (i, c) in cards.iter().enumerate() {
+        if cards[.. i].contains(c) {
+            return Err(CardError::DuplicateCard(*c).into());
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use super::{ HandRank, HandValue };
-use playing_card::card::NonJokerCard;
+use playing_card::card::{ CardRank, NonJokerCard };
+use std::fmt;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub struct BestFiveHand {
@@ -26,4 +27,130 @@ impl BestFiveHand {
         ];
         HandValue { hand_rank: self.hand_rank(), card_ranks }
     }
-}
\ No newline at end of file
+
+    /// Description in English such as "Full house, Kings full of Nines" or "Straight, Five high".
+    pub fn description(self) -> String {
+        // cards of made combinations come first, followed by kickers in descending order
+        let r: Vec<CardRank> = self.cards.iter().map(|c| c.rank()).collect();
+        match self.rank {
+            HandRank::RoyalFlush => String::from("Royal flush"),
+            HandRank::StraightFlush => format!("Straight flush, {} high", rank_name(r[0])),
+            HandRank::FourOfAKind => format!("Four of a kind, {} with {} kicker", plural_rank_name(r[0]), with_article(r[4])),
+            HandRank::FullHouse => format!("Full house, {} full of {}", plural_rank_name(r[0]), plural_rank_name(r[3])),
+            HandRank::Flush => format!("Flush, {} high", rank_name(r[0])),
+            HandRank::Straight => format!("Straight, {} high", rank_name(r[0])),
+            HandRank::ThreeOfAKind => format!("Three of a kind, {} with {} kicker", plural_rank_name(r[0]), with_article(r[3])),
+            HandRank::TwoPairs => format!(
+                "Two pair, {} and {} with {} kicker",
+                plural_rank_name(r[0]), plural_rank_name(r[2]), with_article(r[4])
+            ),
+            HandRank::Pair => format!("Pair of {} with {} kicker", plural_rank_name(r[0]), with_article(r[2])),
+            HandRank::HighCard => format!("High card, {} high", rank_name(r[0])),
+        }
+    }
+
+    /// Ranks of the five cards with made combinations first, e.g. "KKK99" or "5432A".
+    pub fn short_description(self) -> String {
+        self.cards.iter().map(|c| c.rank().to_char()).collect()
+    }
+}
+
+impl fmt::Display for BestFiveHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+/// Name of a card rank such as "Ace" or "Ten".
+pub(super) fn rank_name(rank: CardRank) -> &'static str {
+    match rank.to_int() {
+        1 => "Ace",
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        _ => "King",
+    }
+}
+
+fn plural_rank_name(rank: CardRank) -> String {
+    match rank.to_int() {
+        6 => String::from("Sixes"),
+        _ => format!("{}s", rank_name(rank)),
+    }
+}
+
+fn with_article(rank: CardRank) -> String {
+    match rank.to_int() {
+        1 | 8 => format!("an {}", rank_name(rank)),
+        _ => format!("a {}", rank_name(rank)),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::card::TotalHand;
+    use super::*;
+    use playing_card::card::Suit;
+
+    fn best(cards: &[(Suit, u8)]) -> BestFiveHand {
+        let cards: Vec<NonJokerCard> = cards.iter().map(|(s, r)| NonJokerCard::new(*s, CardRank::new(*r))).collect();
+        TotalHand::new(&cards).find_best_five_hand().unwrap()
+    }
+
+    #[test]
+    fn full_house_is_described_with_set_and_pair() {
+        let hand = best(&[(Suit::Heart, 9), (Suit::Spade, 13), (Suit::Club, 13), (Suit::Heart, 13), (Suit::Diamond, 9)]);
+        assert_eq!("Full house, Kings full of Nines", hand.description());
+        assert_eq!("KKK99", hand.short_description());
+    }
+
+    #[test]
+    fn two_pair_is_described_with_kicker() {
+        let hand = best(&[(Suit::Heart, 12), (Suit::Spade, 12), (Suit::Club, 4), (Suit::Heart, 4), (Suit::Diamond, 11), (Suit::Diamond, 2)]);
+        assert_eq!("Two pair, Queens and Fours with a Jack kicker", hand.description());
+        assert_eq!("QQ44J", hand.short_description());
+    }
+
+    #[test]
+    fn wheel_is_five_high() {
+        let hand = best(&[(Suit::Heart, 1), (Suit::Spade, 2), (Suit::Club, 3), (Suit::Heart, 4), (Suit::Diamond, 5), (Suit::Diamond, 13)]);
+        assert_eq!("Straight, Five high", hand.description());
+        assert_eq!("5432A", hand.short_description());
+    }
+
+    #[test]
+    fn flush_is_described_by_highest_card() {
+        let hand = best(&[(Suit::Heart, 1), (Suit::Heart, 10), (Suit::Heart, 7), (Suit::Heart, 6), (Suit::Heart, 2)]);
+        assert_eq!("Flush, Ace high", hand.description());
+        assert_eq!("AT762", hand.short_description());
+    }
+
+    #[test]
+    fn kickers_take_articles() {
+        let pair = best(&[(Suit::Heart, 6), (Suit::Spade, 6), (Suit::Club, 8), (Suit::Heart, 4), (Suit::Diamond, 3)]);
+        assert_eq!("Pair of Sixes with an Eight kicker", pair.description());
+        let quads = best(&[(Suit::Heart, 2), (Suit::Spade, 2), (Suit::Club, 2), (Suit::Diamond, 2), (Suit::Diamond, 1)]);
+        assert_eq!("Four of a kind, Twos with an Ace kicker", quads.to_string());
+    }
+
+    #[test]
+    fn every_hand_rank_is_described() {
+        let royal = best(&[(Suit::Spade, 1), (Suit::Spade, 13), (Suit::Spade, 12), (Suit::Spade, 11), (Suit::Spade, 10)]);
+        assert_eq!("Royal flush", royal.description());
+        let straight_flush = best(&[(Suit::Spade, 9), (Suit::Spade, 13), (Suit::Spade, 12), (Suit::Spade, 11), (Suit::Spade, 10)]);
+        assert_eq!("Straight flush, King high", straight_flush.description());
+        let set = best(&[(Suit::Heart, 7), (Suit::Spade, 7), (Suit::Club, 7), (Suit::Heart, 12), (Suit::Diamond, 3)]);
+        assert_eq!("Three of a kind, Sevens with a Queen kicker", set.description());
+        let high_card = best(&[(Suit::Heart, 11), (Suit::Spade, 9), (Suit::Club, 7), (Suit::Heart, 4), (Suit::Diamond, 3)]);
+        assert_eq!("High card, Jack high", high_card.description());
+    }
+}
@@ -1,7 +1,10 @@
 use crate::Phase;
+use crate::error::HoldemError;
 use super::StartingHand;
 use playing_card::card::NonJokerCard;
+use playing_card::combination::Combinations;
 use playing_card::deck::Deck;
+use rand::Rng;
 
 pub struct Board {
     deck: Deck<NonJokerCard>,
@@ -10,10 +13,21 @@ pub struct Board {
     river: Option<NonJokerCard>,
 }
 
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Board {
     pub fn new() -> Self {
+        Self::new_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Create a board whose deck is shuffled with given random number generator.
+    pub fn new_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
         let mut deck = Deck::new(NonJokerCard::all());
-        deck.shuffle();
+        deck.shuffle_with(rng);
         Self {
             deck,
             flop: None,
@@ -22,6 +36,55 @@ impl Board {
         }
     }
 
+    /// Create a board on which given cards are already dealt.
+    /// Give 0, 3, 4 or 5 cards in order of flop, turn and river.
+    /// Given cards are removed from the deck.
+    pub fn new_with_cards(cards: &[NonJokerCard]) -> Self {
+        assert!(
+            [0, 3, 4, 5].contains(&cards.len()),
+            "The number of cards on board must be 0, 3, 4 or 5"
+        );
+        Self::with_cards(cards)
+    }
+
+    /// Returns an error if the number of cards is not 0, 3, 4 or 5, or the same card is given twice.
+    pub fn try_new_with_cards(cards: &[NonJokerCard]) -> Result<Self, HoldemError> {
+        if ![0, 3, 4, 5].contains(&cards.len()) {
+            return Err(HoldemError::InvalidBoardSize(cards.len()));
+        }
+        super::check_no_duplicates(cards)?;
+        Ok(Self::with_cards(cards))
+    }
+
+    /// Create a board on which given cards are dealt and whose deck has given cards in order of dealing,
+    /// e.g. to restore a hand in progress.
+    /// Returns an error if the number of board cards is not 0, 3, 4 or 5, or the same card is given twice.
+    pub fn try_new_with_deck(cards: &[NonJokerCard], deck: Vec<NonJokerCard>) -> Result<Self, HoldemError> {
+        let mut board = Self::try_new_with_cards(cards)?;
+        super::check_no_duplicates(&[cards, &deck[..]].concat())?;
+        board.deck = Deck::new(deck);
+        Ok(board)
+    }
+
+    fn with_cards(cards: &[NonJokerCard]) -> Self {
+        let mut board = Self::new();
+        board.deck.search(|c| cards.contains(c));
+        if cards.len() >= 3 {
+            board.flop = Some([cards[0], cards[1], cards[2]]);
+        }
+        board.turn = cards.get(3).copied();
+        board.river = cards.get(4).copied();
+        board
+    }
+
+    /// Create an empty board whose deck does not contain given cards,
+    /// e.g. hole cards which are already known.
+    pub fn new_without_cards(dead_cards: &[NonJokerCard]) -> Self {
+        let mut board = Self::new();
+        board.deck.search(|c| dead_cards.contains(c));
+        board
+    }
+
     pub fn flop(&self) -> Option<[NonJokerCard;3]> {
         self.flop
     }
@@ -50,6 +113,24 @@ impl Board {
         v
     }
 
+    /// Cards left in the deck in order of dealing.
+    pub fn remaining_cards(&self) -> &[NonJokerCard] {
+        self.deck.cards()
+    }
+
+    /// All runouts of the cards dealt from now until given phase, made of the cards left in the deck except given dead cards.
+    /// Cards of each runout are in order of the deck. There is a single empty runout when the phase has come.
+    pub fn runouts_until(&self, until: Phase, dead_cards: &[NonJokerCard]) -> Combinations<'static, NonJokerCard> {
+        let count = board_card_count(until).saturating_sub(self.cards().len());
+        let live_cards = self.deck.cards().iter().filter(|c| !dead_cards.contains(c)).copied().collect();
+        Combinations::new(live_cards, count)
+    }
+
+    /// All runouts to the river, made of the cards left in the deck except given dead cards.
+    pub fn runouts(&self, dead_cards: &[NonJokerCard]) -> Combinations<'static, NonJokerCard> {
+        self.runouts_until(Phase::River, dead_cards)
+    }
+
     pub fn current_phase(&self) -> Phase {
         if self.flop.is_none() {
             Phase::PreFlop
@@ -63,45 +144,52 @@ impl Board {
     }
 
     pub fn deal_starting_hands(&mut self, num_of_players: usize) -> Vec<StartingHand> {
-        let mut v = Vec::new();
-        while v.len() < num_of_players {
-            let hand = StartingHand::new(
-                self.deck.deal_one().expect("Card deck has run out while dealing hand!!"),
-                self.deck.deal_one().expect("Card deck has run out while dealing hand!!")
-            );
-            v.push(hand);
-        }
-        v
+        self.try_deal_starting_hands(num_of_players).expect("Card deck has run out while dealing hand!!")
+    }
+
+    /// Returns an error without dealing any cards if the deck does not have enough cards.
+    pub fn try_deal_starting_hands(&mut self, num_of_players: usize) -> Result<Vec<StartingHand>, HoldemError> {
+        let cards = self.deck.try_deal_many(num_of_players * 2)?;
+        Ok(cards.chunks(2).map(|c| StartingHand::new(c[0], c[1])).collect())
     }
 
     pub fn deal_next_card(&mut self) {
+        self.try_deal_next_card().expect("Card deck has run out while dealing board!!")
+    }
+
+    /// Deals the flop, turn or river. Does nothing on river.
+    /// Returns an error without dealing any cards if the deck does not have enough cards.
+    pub fn try_deal_next_card(&mut self) -> Result<(), HoldemError> {
         match self.current_phase() {
-            Phase::PreFlop => { 
-                let cards = [
-                    self.deck.deal_one().expect("Card deck has run out while dealing flop!!"),
-                    self.deck.deal_one().expect("Card deck has run out while dealing flop!!"),
-                    self.deck.deal_one().expect("Card deck has run out while dealing flop!!"),
-                ];
-                self.flop = Some(cards);
+            Phase::PreFlop => {
+                let cards = self.deck.try_deal_many(3)?;
+                self.flop = Some([cards[0], cards[1], cards[2]]);
             },
-            Phase::Flop => {
-                let card = self.deck.deal_one()
-                               .expect("Card deck has run out while dealing turn!!");
-                self.turn = Some(card);
-            },
-            Phase::Turn => {
-                let card = self.deck.deal_one()
-                               .expect("Card deck has run out while dealing river!!");
-                self.river = Some(card);
-            }
-            Phase::River => ()
+            Phase::Flop => self.turn = Some(self.deck.try_deal_many(1)?[0]),
+            Phase::Turn => self.river = Some(self.deck.try_deal_many(1)?[0]),
+            Phase::River => (),
         }
+        Ok(())
     }
 
     pub fn deal_cards_until(&mut self, until: Phase) {
+        self.try_deal_cards_until(until).expect("Card deck has run out while dealing board!!")
+    }
+
+    pub fn try_deal_cards_until(&mut self, until: Phase) -> Result<(), HoldemError> {
         while self.current_phase() < until {
-            self.deal_next_card();
+            self.try_deal_next_card()?;
         }
+        Ok(())
+    }
+}
+
+fn board_card_count(phase: Phase) -> usize {
+    match phase {
+        Phase::PreFlop => 0,
+        Phase::Flop => 3,
+        Phase::Turn => 4,
+        Phase::River => 5,
     }
 }
 
@@ -109,6 +197,7 @@ impl Board {
 #[cfg(test)]
 mod test {
     use super::*;
+    use playing_card::error::CardError;
 
     #[test]
     fn deals_hand_to_given_number_of_players() {
@@ -117,6 +206,26 @@ mod test {
         assert_eq!(2, hands.len());
     }
 
+    #[test]
+    fn dead_cards_are_not_dealt() {
+        let cards = NonJokerCard::all();
+        let mut board = Board::new_without_cards(&cards[0..2]);
+        let hands = board.deal_starting_hands(25);
+        for hand in hands {
+            assert!(!cards[0..2].contains(&hand.higher_card()));
+            assert!(!cards[0..2].contains(&hand.lower_card()));
+        }
+    }
+
+    #[test]
+    fn same_seed_deals_same_hands() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        let mut a = Board::new_with_rng(&mut StdRng::seed_from_u64(7));
+        let mut b = Board::new_with_rng(&mut StdRng::seed_from_u64(7));
+        assert_eq!(a.deal_starting_hands(9), b.deal_starting_hands(9));
+    }
+
     #[test]
     fn initial_phase_is_pre_flop() {
         let board = Board::new();
@@ -207,6 +316,70 @@ mod test {
         assert_eq!(5, board.cards().len());
     }
 
+    #[test]
+    fn board_with_3_cards_is_on_flop() {
+        let cards = NonJokerCard::all();
+        let board = Board::new_with_cards(&cards[0..3]);
+        assert_eq!(Phase::Flop, board.current_phase());
+        assert_eq!(&cards[0..3], &board.cards()[..]);
+    }
+
+    #[test]
+    fn board_with_5_cards_is_on_river() {
+        let cards = NonJokerCard::all();
+        let board = Board::new_with_cards(&cards[0..5]);
+        assert_eq!(Phase::River, board.current_phase());
+        assert_eq!(&cards[0..5], &board.cards()[..]);
+    }
+
+    #[test]
+    fn given_cards_are_not_dealt_again() {
+        let cards = NonJokerCard::all();
+        let mut board = Board::new_with_cards(&cards[0..4]);
+        let hands = board.deal_starting_hands(24);
+        for hand in hands {
+            assert!(!cards[0..4].contains(&hand.higher_card()));
+            assert!(!cards[0..4].contains(&hand.lower_card()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "number of cards")]
+    fn should_panic_when_2_cards_are_given() {
+        let cards = NonJokerCard::all();
+        Board::new_with_cards(&cards[0..2]);
+    }
+
+    #[test]
+    fn invalid_cards_are_errors() {
+        let cards = NonJokerCard::all();
+        assert_eq!(Some(HoldemError::InvalidBoardSize(2)), Board::try_new_with_cards(&cards[0..2]).err());
+        let duplicate = [cards[0], cards[1], cards[0]];
+        assert_eq!(Some(HoldemError::Card(CardError::DuplicateCard(cards[0]))), Board::try_new_with_cards(&duplicate).err());
+    }
+
+    #[test]
+    fn running_out_of_cards_is_an_error() {
+        let mut board = Board::new();
+        assert_eq!(Err(HoldemError::Card(CardError::DeckExhausted)), board.try_deal_starting_hands(27));
+        // nothing is dealt by the failed deal
+        assert_eq!(26, board.try_deal_starting_hands(26).unwrap().len());
+        assert_eq!(Err(HoldemError::Card(CardError::DeckExhausted)), board.try_deal_next_card());
+        assert_eq!(Phase::PreFlop, board.current_phase());
+    }
+
+    #[test]
+    fn board_is_restored_with_deck() {
+        let mut board = Board::new();
+        board.deal_cards_until(Phase::Turn);
+        let mut restored = Board::try_new_with_deck(&board.cards(), board.remaining_cards().to_vec()).unwrap();
+        board.deal_next_card();
+        restored.deal_next_card();
+        assert_eq!(board.cards(), restored.cards());
+        let cards = NonJokerCard::all();
+        assert!(Board::try_new_with_deck(&cards[0..3], cards[2..].to_vec()).is_err());
+    }
+
     #[test]
     fn deal_until_flop() {
         let mut board = Board::new();
@@ -233,4 +406,25 @@ mod test {
         assert_eq!(phase, board.current_phase());
         assert!(board.river().is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn runouts_from_flop_to_river_are_all_pairs_of_live_cards() {
+        let mut board = Board::new();
+        let hand = board.deal_starting_hands(1)[0];
+        board.deal_cards_until(Phase::Flop);
+        // 52 - 2 hole cards - 3 flop cards - 2 dead cards
+        let dead = board.remaining_cards()[.. 2].to_vec();
+        let runouts = board.runouts(&dead);
+        assert_eq!(45 * 44 / 2, runouts.len());
+        assert!(runouts.flatten().all(|c| !dead.contains(&c) && !hand.both_cards().contains(&c)));
+        assert_eq!(45, board.runouts_until(Phase::Turn, &dead).len());
+    }
+
+    #[test]
+    fn runout_of_river_is_empty() {
+        let mut board = Board::new();
+        board.deal_cards_until(Phase::River);
+        assert_eq!(vec![Vec::<NonJokerCard>::new()], board.runouts(&[]).collect::<Vec<_>>());
+        assert_eq!(1, board.runouts_until(Phase::Flop, &[]).len());
+    }
+}
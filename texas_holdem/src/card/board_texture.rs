@@ -0,0 +1,331 @@
+use crate::Phase;
+use super::{ Board, TotalHand };
+use playing_card::card::{ CardRank, NonJokerCard, Suit };
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum BoardPairing {
+    Unpaired,
+    Paired,
+    TwoPaired,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum SuitTexture {
+    /// No two cards share a suit.
+    Rainbow,
+    /// Two cards of a suit at most. A flush draw is possible.
+    TwoTone,
+    /// Three or more cards of a suit, but not all cards. A flush is possible.
+    FlushPossible,
+    /// All cards are of a suit. A flush is possible.
+    Monotone,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum Wetness {
+    Dry,
+    SemiWet,
+    Wet,
+}
+
+/// Features of the board cards which do not depend on hole cards.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BoardTexture {
+    pairing: BoardPairing,
+    suit_texture: SuitTexture,
+    max_suit_count: usize,
+    head_ranks_of_possible_straight: Vec<CardRank>,
+    high_card_rank: CardRank,
+}
+
+impl BoardTexture {
+    /// Board must have 3 or more cards.
+    pub fn analyze(board: &Board) -> Self {
+        Self::analyze_cards(&board.cards())
+    }
+
+    /// Returns how the card dealt on given phase changed the texture.
+    /// Returns None if the phase is not the turn or the river, or the card is not dealt yet.
+    pub fn change_on(board: &Board, phase: Phase) -> Option<TextureChange> {
+        let card_count = match phase {
+            Phase::Turn => 4,
+            Phase::River => 5,
+            _ => return None,
+        };
+        let cards = board.cards();
+        if cards.len() < card_count {
+            return None;
+        }
+        Some(TextureChange {
+            before: Self::analyze_cards(&cards[.. card_count - 1]),
+            after: Self::analyze_cards(&cards[.. card_count]),
+        })
+    }
+
+    fn analyze_cards(cards: &[NonJokerCard]) -> Self {
+        assert!(cards.len() >= 3, "Board texture cannot be analyzed before flop");
+        let total_hand = TotalHand::new(cards);
+        let pairing = if total_hand.rank_of_quads().is_some() {
+            BoardPairing::Quads
+        } else {
+            match (total_hand.ranks_of_sets().len(), total_hand.ranks_of_pairs().len()) {
+                (0, 0) => BoardPairing::Unpaired,
+                (0, 1) => BoardPairing::Paired,
+                (0, _) => BoardPairing::TwoPaired,
+                (_, 0) => BoardPairing::Trips,
+                _ => BoardPairing::FullHouse,
+            }
+        };
+
+        let max_suit_count = Suit::all().iter()
+            .map(|s| cards.iter().filter(|c| c.suit() == *s).count())
+            .max()
+            .unwrap();
+        let suit_texture = match max_suit_count {
+            1 => SuitTexture::Rainbow,
+            2 => SuitTexture::TwoTone,
+            n if n == cards.len() => SuitTexture::Monotone,
+            _ => SuitTexture::FlushPossible,
+        };
+
+        Self {
+            pairing,
+            suit_texture,
+            max_suit_count,
+            head_ranks_of_possible_straight: total_hand.head_ranks_of_possible_straight().to_vec(),
+            // cards of TotalHand are sorted in descending order
+            high_card_rank: total_hand.cards()[0].rank(),
+        }
+    }
+
+    pub fn pairing(&self) -> BoardPairing {
+        self.pairing
+    }
+
+    pub fn is_paired(&self) -> bool {
+        self.pairing != BoardPairing::Unpaired
+    }
+
+    pub fn suit_texture(&self) -> SuitTexture {
+        self.suit_texture
+    }
+
+    /// The largest number of board cards sharing a suit.
+    pub fn max_suit_count(&self) -> usize {
+        self.max_suit_count
+    }
+
+    /// Number of different straights a player can make with two hole cards.
+    pub fn possible_straight_count(&self) -> usize {
+        self.head_ranks_of_possible_straight.len()
+    }
+
+    /// Head ranks of straights a player can make with two hole cards.
+    pub fn head_ranks_of_possible_straight(&self) -> &[CardRank] {
+        &self.head_ranks_of_possible_straight
+    }
+
+    pub fn high_card_rank(&self) -> CardRank {
+        self.high_card_rank
+    }
+
+    /// Rough estimation of how many draws the board allows.
+    pub fn wetness(&self) -> Wetness {
+        let suit_score = match self.suit_texture {
+            SuitTexture::Rainbow => 0,
+            SuitTexture::TwoTone => 1,
+            SuitTexture::FlushPossible => 2,
+            // every card is of the suit, so a single card of it draws to a flush on flop and makes one on turn or river
+            SuitTexture::Monotone => 3,
+        };
+        let straight_score = match self.possible_straight_count() {
+            0 => 0,
+            1 | 2 => 1,
+            _ => 2,
+        };
+        match suit_score + straight_score {
+            0 => Wetness::Dry,
+            1 | 2 => Wetness::SemiWet,
+            _ => Wetness::Wet,
+        }
+    }
+}
+
+/// Textures before and after a card is dealt.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextureChange {
+    before: BoardTexture,
+    after: BoardTexture,
+}
+
+impl TextureChange {
+    pub fn before(&self) -> &BoardTexture {
+        &self.before
+    }
+
+    pub fn after(&self) -> &BoardTexture {
+        &self.after
+    }
+
+    pub fn pairing_changed(&self) -> bool {
+        self.before.pairing != self.after.pairing
+    }
+
+    pub fn suit_texture_changed(&self) -> bool {
+        self.before.suit_texture != self.after.suit_texture
+    }
+
+    /// Returns true if the new card made a flush possible.
+    pub fn completes_flush_possibility(&self) -> bool {
+        self.before.max_suit_count < 3 && self.after.max_suit_count >= 3
+    }
+
+    pub fn connectedness_changed(&self) -> bool {
+        self.before.head_ranks_of_possible_straight != self.after.head_ranks_of_possible_straight
+    }
+
+    pub fn high_card_changed(&self) -> bool {
+        self.before.high_card_rank != self.after.high_card_rank
+    }
+
+    pub fn wetness_changed(&self) -> bool {
+        self.before.wetness() != self.after.wetness()
+    }
+
+    pub fn any_changed(&self) -> bool {
+        self.pairing_changed()
+            || self.suit_texture_changed()
+            || self.connectedness_changed()
+            || self.high_card_changed()
+            || self.wetness_changed()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn analyze(cards: &[NonJokerCard]) -> BoardTexture {
+        BoardTexture::analyze(&Board::new_with_cards(cards))
+    }
+
+    #[test]
+    #[should_panic(expected = "before flop")]
+    fn should_panic_on_pre_flop() {
+        analyze(&[]);
+    }
+
+    #[test]
+    fn dry_rainbow_board() {
+        let texture = analyze(&[card(Suit::Spade, 13), card(Suit::Heart, 7), card(Suit::Club, 2)]);
+        assert_eq!(BoardPairing::Unpaired, texture.pairing());
+        assert_eq!(SuitTexture::Rainbow, texture.suit_texture());
+        assert_eq!(0, texture.possible_straight_count());
+        assert_eq!(CardRank::King, texture.high_card_rank());
+        assert_eq!(Wetness::Dry, texture.wetness());
+    }
+
+    #[test]
+    fn connected_two_tone_board_is_wet() {
+        let texture = analyze(&[card(Suit::Heart, 9), card(Suit::Heart, 8), card(Suit::Club, 7)]);
+        assert_eq!(SuitTexture::TwoTone, texture.suit_texture());
+        assert_eq!(3, texture.possible_straight_count());
+        assert_eq!(Wetness::Wet, texture.wetness());
+    }
+
+    #[test]
+    fn three_cards_of_suit_is_monotone() {
+        let texture = analyze(&[card(Suit::Heart, 13), card(Suit::Heart, 7), card(Suit::Heart, 2)]);
+        assert_eq!(SuitTexture::Monotone, texture.suit_texture());
+        assert_eq!(3, texture.max_suit_count());
+    }
+
+    #[test]
+    fn paired_board() {
+        let texture = analyze(&[card(Suit::Heart, 13), card(Suit::Club, 13), card(Suit::Heart, 2)]);
+        assert_eq!(BoardPairing::Paired, texture.pairing());
+        assert!(texture.is_paired());
+    }
+
+    #[test]
+    fn trips_board() {
+        let texture = analyze(&[card(Suit::Heart, 13), card(Suit::Club, 13), card(Suit::Spade, 13)]);
+        assert_eq!(BoardPairing::Trips, texture.pairing());
+    }
+
+    #[test]
+    fn two_paired_board() {
+        let texture = analyze(&[
+            card(Suit::Heart, 13),
+            card(Suit::Club, 13),
+            card(Suit::Spade, 2),
+            card(Suit::Diamond, 2),
+        ]);
+        assert_eq!(BoardPairing::TwoPaired, texture.pairing());
+    }
+
+    #[test]
+    fn no_change_is_reported_on_flop() {
+        let board = Board::new_with_cards(&[card(Suit::Heart, 13), card(Suit::Club, 7), card(Suit::Spade, 2)]);
+        assert_eq!(None, BoardTexture::change_on(&board, Phase::Flop));
+        assert_eq!(None, BoardTexture::change_on(&board, Phase::Turn));
+    }
+
+    #[test]
+    fn turn_card_pairing_board_is_reported() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Heart, 13),
+            card(Suit::Club, 7),
+            card(Suit::Spade, 2),
+            card(Suit::Diamond, 7),
+        ]);
+        let change = BoardTexture::change_on(&board, Phase::Turn).unwrap();
+        assert!(change.pairing_changed());
+        assert!(!change.high_card_changed());
+        assert!(!change.suit_texture_changed());
+        assert!(change.any_changed());
+    }
+
+    #[test]
+    fn river_card_completing_flush_is_reported() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Heart, 13),
+            card(Suit::Heart, 7),
+            card(Suit::Spade, 2),
+            card(Suit::Diamond, 9),
+            card(Suit::Heart, 3),
+        ]);
+        let change = BoardTexture::change_on(&board, Phase::River).unwrap();
+        assert!(change.completes_flush_possibility());
+        assert!(change.suit_texture_changed());
+        assert_eq!(SuitTexture::TwoTone, change.before().suit_texture());
+        assert_eq!(SuitTexture::FlushPossible, change.after().suit_texture());
+    }
+
+    #[test]
+    fn four_cards_of_suit_on_turn_is_monotone() {
+        let texture = analyze(&[card(Suit::Heart, 13), card(Suit::Heart, 7), card(Suit::Heart, 2), card(Suit::Heart, 9)]);
+        assert_eq!(SuitTexture::Monotone, texture.suit_texture());
+        assert_eq!(Wetness::Wet, texture.wetness());
+    }
+
+    #[test]
+    fn blank_turn_changes_nothing() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Heart, 13),
+            card(Suit::Club, 8),
+            card(Suit::Spade, 2),
+            card(Suit::Diamond, 3),
+        ]);
+        let change = BoardTexture::change_on(&board, Phase::Turn).unwrap();
+        assert!(!change.any_changed());
+    }
+}
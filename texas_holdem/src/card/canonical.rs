@@ -0,0 +1,207 @@
+use super::{ Board, StartingHand };
+use playing_card::card::{ CardRank, NonJokerCard, Suit };
+use std::collections::HashSet;
+
+/// Hole cards and board cards whose suits are renamed in the canonical way.
+/// Situations that differ only in names of suits have the same canonical form.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CanonicalForm {
+    hole_cards: Vec<NonJokerCard>,
+    board_cards: Vec<NonJokerCard>,
+    combo_count: usize,
+}
+
+/// Position of a card in NonJokerCard::all().
+fn card_index(card: NonJokerCard) -> usize {
+    let suit_index = Suit::all().iter().position(|s| *s == card.suit()).unwrap();
+    suit_index * 13 + card.rank().to_int() as usize - 1
+}
+
+fn card_of_index(index: usize) -> NonJokerCard {
+    NonJokerCard::new(Suit::all()[index / 13], CardRank::new((index % 13 + 1) as u8))
+}
+
+/// All 24 permutations of suit indexes.
+fn suit_permutations() -> Vec<[usize; 4]> {
+    let mut v = Vec::new();
+    for a in 0 .. 4 {
+        for b in 0 .. 4 {
+            for c in 0 .. 4 {
+                if a != b && a != c && b != c {
+                    // the remaining index
+                    let d = 6 - a - b - c;
+                    v.push([a, b, c, d]);
+                }
+            }
+        }
+    }
+    v
+}
+
+/// Renames suits of the cards, then sorts hole cards and flop cards, whose orders do not matter.
+fn encode(hole_cards: &[usize], board_cards: &[usize], permutation: &[usize; 4]) -> Vec<usize> {
+    let rename = |i: &usize| permutation[i / 13] * 13 + i % 13;
+    let mut hole: Vec<usize> = hole_cards.iter().map(rename).collect();
+    hole.sort_unstable();
+    let mut board: Vec<usize> = board_cards.iter().map(rename).collect();
+    let flop_len = board.len().min(3);
+    board[.. flop_len].sort_unstable();
+    hole.into_iter().chain(board).collect()
+}
+
+impl CanonicalForm {
+    /// Hole cards must be 0 or 2 cards, and board cards must be 0, 3, 4 or 5 cards in dealt order.
+    pub fn new(hole_cards: &[NonJokerCard], board_cards: &[NonJokerCard]) -> Self {
+        assert!(hole_cards.is_empty() || hole_cards.len() == 2, "Invalid number of hole cards: {}", hole_cards.len());
+        assert!(matches!(board_cards.len(), 0 | 3 ..= 5), "Invalid number of board cards: {}", board_cards.len());
+        let hole: Vec<usize> = hole_cards.iter().map(|c| card_index(*c)).collect();
+        let board: Vec<usize> = board_cards.iter().map(|c| card_index(*c)).collect();
+
+        let encodings: HashSet<Vec<usize>> = suit_permutations().iter()
+            .map(|p| encode(&hole, &board, p))
+            .collect();
+        let canonical = encodings.iter().min().unwrap();
+        Self {
+            hole_cards: canonical[.. hole.len()].iter().map(|i| card_of_index(*i)).collect(),
+            board_cards: canonical[hole.len() ..].iter().map(|i| card_of_index(*i)).collect(),
+            combo_count: encodings.len(),
+        }
+    }
+
+    pub fn of_hand(hand: StartingHand, board: &Board) -> Self {
+        Self::new(&hand.both_cards(), &board.cards())
+    }
+
+    pub fn of_board(board: &Board) -> Self {
+        Self::new(&[], &board.cards())
+    }
+
+    /// All 1755 canonical flops without hole cards.
+    pub fn all_flops() -> impl Iterator<Item = Self> {
+        (0 .. 52).flat_map(|i| (i + 1 .. 52).flat_map(move |j| (j + 1 .. 52).map(move |k| [i, j, k])))
+            .filter_map(|indexes| {
+                let cards: Vec<NonJokerCard> = indexes.iter().map(|i| card_of_index(*i)).collect();
+                let form = Self::new(&[], &cards);
+                // each class is yielded once, when the flop itself is its canonical form
+                if form.board_cards == cards { Some(form) } else { None }
+            })
+    }
+
+    pub fn hole_cards(&self) -> &[NonJokerCard] {
+        &self.hole_cards
+    }
+
+    pub fn starting_hand(&self) -> Option<StartingHand> {
+        match self.hole_cards[..] {
+            [a, b] => Some(StartingHand::new(a, b)),
+            _ => None,
+        }
+    }
+
+    /// Board cards in dealt order. Flop cards are sorted.
+    pub fn board_cards(&self) -> &[NonJokerCard] {
+        &self.board_cards
+    }
+
+    pub fn board(&self) -> Board {
+        Board::new_with_cards(&self.board_cards)
+    }
+
+    /// Number of situations isomorphic to this form, including itself.
+    pub fn combo_count(&self) -> usize {
+        self.combo_count
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::HandClass;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    #[test]
+    fn there_are_24_suit_permutations() {
+        let permutations: HashSet<[usize; 4]> = suit_permutations().into_iter().collect();
+        assert_eq!(24, permutations.len());
+    }
+
+    #[test]
+    fn card_index_is_inverse_of_card_of_index() {
+        for (i, c) in NonJokerCard::all().iter().enumerate() {
+            assert_eq!(i, card_index(*c));
+            assert_eq!(*c, card_of_index(i));
+        }
+    }
+
+    #[test]
+    fn starting_hands_are_collapsed_into_169_classes() {
+        let forms: HashSet<CanonicalForm> = StartingHand::all().into_iter()
+            .map(|h| CanonicalForm::of_hand(h, &Board::new()))
+            .collect();
+        assert_eq!(169, forms.len());
+        for form in forms {
+            let class = form.starting_hand().unwrap().class();
+            assert_eq!(class.combo_count(), form.combo_count());
+        }
+    }
+
+    #[test]
+    fn hands_of_same_class_have_same_form() {
+        let class = HandClass::from_summary("AKo").unwrap();
+        let forms: HashSet<CanonicalForm> = class.starting_hands().into_iter()
+            .map(|h| CanonicalForm::of_hand(h, &Board::new()))
+            .collect();
+        assert_eq!(1, forms.len());
+    }
+
+    #[test]
+    fn there_are_1755_canonical_flops() {
+        let flops: Vec<CanonicalForm> = CanonicalForm::all_flops().collect();
+        assert_eq!(1755, flops.len());
+        let total: usize = flops.iter().map(|f| f.combo_count()).sum();
+        assert_eq!(22100, total);
+    }
+
+    #[test]
+    fn renaming_suits_keeps_form() {
+        let a = CanonicalForm::new(
+            &[card(Suit::Heart, 1), card(Suit::Heart, 13)],
+            &[card(Suit::Heart, 2), card(Suit::Spade, 7), card(Suit::Club, 9), card(Suit::Spade, 5)],
+        );
+        let b = CanonicalForm::new(
+            &[card(Suit::Club, 13), card(Suit::Club, 1)],
+            &[card(Suit::Diamond, 9), card(Suit::Club, 2), card(Suit::Heart, 7), card(Suit::Heart, 5)],
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn turn_card_is_not_mixed_with_flop() {
+        let a = CanonicalForm::new(&[], &[
+            card(Suit::Heart, 2), card(Suit::Heart, 7), card(Suit::Heart, 9), card(Suit::Spade, 5),
+        ]);
+        let b = CanonicalForm::new(&[], &[
+            card(Suit::Heart, 2), card(Suit::Heart, 7), card(Suit::Spade, 5), card(Suit::Heart, 9),
+        ]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn board_of_form_can_be_dealt() {
+        let form = CanonicalForm::new(&[card(Suit::Spade, 1), card(Suit::Heart, 1)], &[
+            card(Suit::Heart, 2), card(Suit::Club, 7), card(Suit::Heart, 9),
+        ]);
+        assert_eq!(form.board_cards(), &form.board().cards()[..]);
+        assert_eq!(24, form.combo_count());
+    }
+
+    #[test]
+    #[should_panic(expected = "number of board cards")]
+    fn should_panic_with_invalid_number_of_board_cards() {
+        CanonicalForm::new(&[], &[card(Suit::Heart, 2)]);
+    }
+}
@@ -0,0 +1,161 @@
+use super::{ Board, BestFiveHand, HandRank, HandValue, StartingHand, TotalHand };
+use super::best_five_hand::rank_name;
+use crate::Phase;
+use playing_card::card::CardRank;
+use std::cmp::Ordering;
+
+/// What decides the result of comparing a hand with an opponent's hand.
+/// Ranks are given in order of the hand and the opponent's hand.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Decider {
+    HandRank { hand: HandRank, opponent: HandRank },
+    /// Cards making the hand rank differ, e.g. the lower pairs of two pairs.
+    /// `position` is the index of the differing card in the five cards.
+    MadeCards { position: usize, hand: CardRank, opponent: CardRank },
+    /// A kicker differs. `number` is 1 for the highest kicker.
+    Kicker { number: usize, hand: CardRank, opponent: CardRank },
+    /// The hands have the same value and split the pot.
+    Tie,
+    /// The hands tie because both players play the five board cards.
+    BoardPlays,
+}
+
+/// Result of comparing a hand with an opponent's hand, with the reason.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct HandComparison {
+    /// Greater if the hand wins.
+    pub ordering: Ordering,
+    pub decider: Decider,
+}
+
+impl HandComparison {
+    pub fn between(hand: BestFiveHand, opponent: BestFiveHand) -> Self {
+        Self::between_values(hand.value(), opponent.value())
+    }
+
+    fn between_values(hand: HandValue, opponent: HandValue) -> Self {
+        let ordering = hand.cmp(&opponent);
+        if hand.hand_rank != opponent.hand_rank {
+            let decider = Decider::HandRank { hand: hand.hand_rank, opponent: opponent.hand_rank };
+            return Self { ordering, decider };
+        }
+        // the first card rank compared in HandValue::cmp which differs
+        let position = (0 .. 5).find(|i| hand.card_ranks[*i] != opponent.card_ranks[*i]);
+        let decider = match position {
+            None => Decider::Tie,
+            Some(p) if p < made_card_count(hand.hand_rank) => {
+                Decider::MadeCards { position: p, hand: hand.card_ranks[p], opponent: opponent.card_ranks[p] }
+            },
+            Some(p) => Decider::Kicker {
+                number: p - made_card_count(hand.hand_rank) + 1,
+                hand: hand.card_ranks[p],
+                opponent: opponent.card_ranks[p],
+            },
+        };
+        Self { ordering, decider }
+    }
+
+    /// Compares hands of two players at showdown on a board with 5 cards.
+    pub fn at_showdown(hand: StartingHand, opponent: StartingHand, board: &Board) -> Self {
+        assert_eq!(Phase::River, board.current_phase(), "Showdown needs a board with 5 cards");
+        let value_of = |cards: &[_]| {
+            TotalHand::new(cards).find_best_five_hand().expect("best five hand not found with 5 or more cards").value()
+        };
+        let board_cards = board.cards();
+        let hand_value = value_of(&[&hand.both_cards()[..], &board_cards[..]].concat());
+        let opponent_value = value_of(&[&opponent.both_cards()[..], &board_cards[..]].concat());
+        let mut comparison = Self::between_values(hand_value, opponent_value);
+        if comparison.decider == Decider::Tie && value_of(&board_cards) == hand_value {
+            comparison.decider = Decider::BoardPlays;
+        }
+        comparison
+    }
+
+    /// Explanation from the side of the hand, e.g. "Lost on the second kicker: Jack vs Queen".
+    pub fn description(&self) -> String {
+        let result = match self.ordering {
+            Ordering::Greater => "Won",
+            Ordering::Less => "Lost",
+            Ordering::Equal => "Tied",
+        };
+        match self.decider {
+            Decider::HandRank { hand, opponent } => format!("{} on hand rank: {} vs {}", result, hand.name(), opponent.name()),
+            Decider::MadeCards { hand, opponent, .. } => format!("{} on the made hand: {} vs {}", result, rank_name(hand), rank_name(opponent)),
+            Decider::Kicker { number, hand, opponent } => format!(
+                "{} on the {} kicker: {} vs {}", result, ORDINALS[number - 1], rank_name(hand), rank_name(opponent)
+            ),
+            Decider::Tie => String::from("Tied with the same hand"),
+            Decider::BoardPlays => String::from("Tied as the board plays"),
+        }
+    }
+}
+
+const ORDINALS: [&str; 4] = ["first", "second", "third", "fourth"];
+
+/// Number of leading cards making the hand rank. The rest are kickers.
+fn made_card_count(rank: HandRank) -> usize {
+    match rank {
+        HandRank::HighCard => 1,
+        HandRank::Pair => 2,
+        HandRank::ThreeOfAKind => 3,
+        HandRank::TwoPairs | HandRank::FourOfAKind => 4,
+        _ => 5,
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ NonJokerCard, Suit };
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn hand(a: NonJokerCard, b: NonJokerCard) -> StartingHand {
+        StartingHand::new(a, b)
+    }
+
+    fn board() -> Board {
+        Board::new_with_cards(&[card(Suit::Spade, 1), card(Suit::Diamond, 1), card(Suit::Club, 9), card(Suit::Heart, 5), card(Suit::Spade, 2)])
+    }
+
+    #[test]
+    fn hand_rank_decides() {
+        let c = HandComparison::at_showdown(hand(card(Suit::Heart, 9), card(Suit::Heart, 3)), hand(card(Suit::Heart, 1), card(Suit::Heart, 13)), &board());
+        assert_eq!(Ordering::Less, c.ordering);
+        assert_eq!(Decider::HandRank { hand: HandRank::TwoPairs, opponent: HandRank::ThreeOfAKind }, c.decider);
+        assert_eq!("Lost on hand rank: Two pair vs Three of a kind", c.description());
+    }
+
+    #[test]
+    fn kicker_decides() {
+        let c = HandComparison::at_showdown(hand(card(Suit::Heart, 13), card(Suit::Heart, 12)), hand(card(Suit::Club, 13), card(Suit::Club, 11)), &board());
+        assert_eq!(Ordering::Greater, c.ordering);
+        assert_eq!(Decider::Kicker { number: 2, hand: CardRank::Queen, opponent: CardRank::Jack }, c.decider);
+        assert_eq!("Won on the second kicker: Queen vs Jack", c.description());
+    }
+
+    #[test]
+    fn made_cards_decide() {
+        let c = HandComparison::at_showdown(hand(card(Suit::Heart, 9), card(Suit::Heart, 3)), hand(card(Suit::Heart, 5), card(Suit::Club, 13)), &board());
+        assert_eq!(Decider::MadeCards { position: 2, hand: CardRank::new(9), opponent: CardRank::new(5) }, c.decider);
+        assert_eq!("Won on the made hand: Nine vs Five", c.description());
+    }
+
+    #[test]
+    fn board_plays_when_no_hole_card_is_used() {
+        let board = Board::new_with_cards(&[card(Suit::Spade, 10), card(Suit::Diamond, 11), card(Suit::Club, 12), card(Suit::Heart, 13), card(Suit::Spade, 1)]);
+        let c = HandComparison::at_showdown(hand(card(Suit::Heart, 2), card(Suit::Heart, 3)), hand(card(Suit::Club, 2), card(Suit::Club, 4)), &board);
+        assert_eq!(Ordering::Equal, c.ordering);
+        assert_eq!(Decider::BoardPlays, c.decider);
+    }
+
+    #[test]
+    fn same_hands_tie() {
+        let c = HandComparison::at_showdown(hand(card(Suit::Heart, 13), card(Suit::Heart, 12)), hand(card(Suit::Club, 13), card(Suit::Club, 12)), &board());
+        assert_eq!(Decider::Tie, c.decider);
+        assert_eq!("Tied with the same hand", c.description());
+    }
+}
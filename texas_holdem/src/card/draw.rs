@@ -0,0 +1,412 @@
+use super::{ Board, StartingHand, TotalHand };
+use playing_card::card::{ CardRank, NonJokerCard, Suit };
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum DrawKind {
+    NutFlushDraw,
+    FlushDraw,
+    OpenEndedStraightDraw,
+    DoubleGutshot,
+    Gutshot,
+    BackdoorFlushDraw,
+    BackdoorStraightDraw,
+}
+
+impl DrawKind {
+    pub fn is_flush_draw(self) -> bool {
+        matches!(self, Self::NutFlushDraw | Self::FlushDraw)
+    }
+
+    pub fn is_straight_draw(self) -> bool {
+        matches!(self, Self::OpenEndedStraightDraw | Self::DoubleGutshot | Self::Gutshot)
+    }
+
+    /// Returns true if the draw needs both of the turn and the river.
+    pub fn is_backdoor(self) -> bool {
+        matches!(self, Self::BackdoorFlushDraw | Self::BackdoorStraightDraw)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Draw {
+    kind: DrawKind,
+    hole_cards_used: u8,
+}
+
+impl Draw {
+    pub fn kind(self) -> DrawKind {
+        self.kind
+    }
+
+    /// The least number of hole cards (0, 1 or 2) needed to have this draw.
+    /// 0 means that the draw is on the board and every player shares it.
+    pub fn hole_cards_used(self) -> u8 {
+        self.hole_cards_used
+    }
+
+    pub fn uses_hole_cards(self) -> bool {
+        self.hole_cards_used > 0
+    }
+}
+
+/// All draws a starting hand has on a board.
+/// No draw is found before the flop or on the river.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Draws {
+    draws: Vec<Draw>,
+}
+
+impl Draws {
+    pub fn classify(hand: StartingHand, board: &Board) -> Self {
+        let board_cards = board.cards();
+        if board_cards.len() < 3 || board_cards.len() >= 5 {
+            return Self { draws: Vec::new() };
+        }
+        let hole_cards = hand.both_cards();
+        let all_cards = [&hole_cards[..], &board_cards[..]].concat();
+        let total_hand = TotalHand::new(&all_cards);
+        let is_flop = board_cards.len() == 3;
+
+        let mut draws = Vec::new();
+        if total_hand.suit_of_flush().is_none() {
+            draws.extend(flush_draw(&hole_cards, &board_cards, is_flop));
+        }
+        if total_hand.head_ranks_of_straight().is_empty() {
+            let front_door = find_draw_of(&hole_cards, &board_cards, straight_draw_kind);
+            if front_door.is_some() {
+                draws.extend(front_door);
+            } else if is_flop {
+                draws.extend(find_draw_of(&hole_cards, &board_cards, backdoor_straight_draw_kind));
+            }
+        }
+        Self { draws }
+    }
+
+    pub fn all(&self) -> &[Draw] {
+        &self.draws
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    pub fn find(&self, kind: DrawKind) -> Option<Draw> {
+        self.draws.iter().find(|d| d.kind == kind).copied()
+    }
+
+    /// Returns true if the hand has both of a flush draw and a straight draw using its hole cards.
+    pub fn is_combo_draw(&self) -> bool {
+        let uses_hole_cards_for = |f: fn(DrawKind) -> bool| {
+            self.draws.iter().any(|d| f(d.kind) && d.uses_hole_cards())
+        };
+        uses_hole_cards_for(DrawKind::is_flush_draw) && uses_hole_cards_for(DrawKind::is_straight_draw)
+    }
+}
+
+fn flush_draw(hole_cards: &[NonJokerCard], board_cards: &[NonJokerCard], is_flop: bool) -> Option<Draw> {
+    for suit in Suit::all().iter() {
+        let count_on_board = board_cards.iter().filter(|c| c.suit() == *suit).count();
+        let hole_cards_of_suit: Vec<&NonJokerCard> = hole_cards.iter().filter(|c| c.suit() == *suit).collect();
+        let hole_cards_used = hole_cards_of_suit.len() as u8;
+        let kind = match count_on_board + hole_cards_of_suit.len() {
+            4 if has_nut_card_of_suit(*suit, &hole_cards_of_suit, board_cards) => DrawKind::NutFlushDraw,
+            4 => DrawKind::FlushDraw,
+            3 if is_flop => DrawKind::BackdoorFlushDraw,
+            _ => continue,
+        };
+        return Some(Draw { kind, hole_cards_used });
+    }
+    None
+}
+
+/// Returns true if the highest card of the suit that is not on the board is in hole cards.
+fn has_nut_card_of_suit(suit: Suit, hole_cards_of_suit: &[&NonJokerCard], board_cards: &[NonJokerCard]) -> bool {
+    let mut ranks = CardRank::all();
+    ranks.sort_by(|a, b| super::cmp_card_ranks(*a, *b).reverse());
+    let nut_rank = ranks.iter()
+                        .find(|r| !board_cards.iter().any(|c| c.suit() == suit && c.rank() == **r))
+                        .expect("all cards of a suit are on the board");
+    hole_cards_of_suit.iter().any(|c| c.rank() == *nut_rank)
+}
+
+/// Finds a draw detected by given function and counts how many hole cards are needed for it.
+fn find_draw_of(
+    hole_cards: &[NonJokerCard],
+    board_cards: &[NonJokerCard],
+    detect: fn(&[NonJokerCard]) -> Option<DrawKind>,
+) -> Option<Draw> {
+    let kind = detect(&[hole_cards, board_cards].concat())?;
+    let hole_cards_used = if detect(board_cards) == Some(kind) {
+        0
+    } else if hole_cards.iter().any(|h| detect(&[&[*h], board_cards].concat()) == Some(kind)) {
+        1
+    } else {
+        2
+    };
+    Some(Draw { kind, hole_cards_used })
+}
+
+fn straight_draw_kind(cards: &[NonJokerCard]) -> Option<DrawKind> {
+    let total_hand = TotalHand::new(cards);
+    let outs = total_hand.straight_draw_ranks();
+    if outs.is_empty() {
+        return None;
+    }
+
+    // 4 sequential ranks with both ends open. Ace is not available for the upper end
+    // but it is for the lower end as a part of 5-high straight.
+    let bits = bit_of_ranks(cards);
+    let four_sequence = 0b1111u16;
+    let is_open_ended = (1 ..= 9).any(|shift| bits & (four_sequence << shift) == four_sequence << shift);
+
+    if is_open_ended {
+        Some(DrawKind::OpenEndedStraightDraw)
+    } else if outs.len() >= 2 {
+        Some(DrawKind::DoubleGutshot)
+    } else {
+        Some(DrawKind::Gutshot)
+    }
+}
+
+fn backdoor_straight_draw_kind(cards: &[NonJokerCard]) -> Option<DrawKind> {
+    let total_hand = TotalHand::new(cards);
+    if !total_hand.head_ranks_of_possible_straight().is_empty() {
+        Some(DrawKind::BackdoorStraightDraw)
+    } else {
+        None
+    }
+}
+
+/// 14 bit integer that indicates existence of cards of each number.
+/// The lowest bit is Ace as 1, and the highest bit is Ace as 14.
+fn bit_of_ranks(cards: &[NonJokerCard]) -> u16 {
+    cards.iter().fold(0u16, |bits, c| match c.rank() {
+        CardRank::Ace => bits | 1 | (1 << 13),
+        r => bits | (1 << (r.to_int() - 1)),
+    })
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn classify(hole: [NonJokerCard; 2], board_cards: &[NonJokerCard]) -> Draws {
+        let hand = StartingHand::new(hole[0], hole[1]);
+        let board = Board::new_with_cards(board_cards);
+        Draws::classify(hand, &board)
+    }
+
+    #[test]
+    fn no_draw_is_found_on_pre_flop() {
+        let draws = classify([card(Suit::Heart, 6), card(Suit::Heart, 5)], &[]);
+        assert!(draws.is_empty());
+    }
+
+    #[test]
+    fn no_draw_is_found_on_river() {
+        let draws = classify(
+            [card(Suit::Heart, 6), card(Suit::Heart, 5)],
+            &[card(Suit::Heart, 7), card(Suit::Heart, 8), card(Suit::Club, 13), card(Suit::Club, 2), card(Suit::Spade, 11)],
+        );
+        assert!(draws.is_empty());
+    }
+
+    #[test]
+    fn four_cards_of_suit_with_hole_cards_is_flush_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 6), card(Suit::Heart, 5)],
+            &[card(Suit::Heart, 13), card(Suit::Heart, 2), card(Suit::Club, 10)],
+        );
+        let draw = draws.find(DrawKind::FlushDraw).expect("flush draw is expected");
+        assert_eq!(2, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn flush_draw_with_highest_missing_card_is_nut_flush_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 1), card(Suit::Club, 5)],
+            &[card(Suit::Heart, 13), card(Suit::Heart, 2), card(Suit::Heart, 10)],
+        );
+        let draw = draws.find(DrawKind::NutFlushDraw).expect("nut flush draw is expected");
+        assert_eq!(1, draw.hole_cards_used());
+        assert_eq!(None, draws.find(DrawKind::FlushDraw));
+    }
+
+    #[test]
+    fn king_is_nut_flush_draw_when_ace_is_on_board() {
+        let draws = classify(
+            [card(Suit::Heart, 13), card(Suit::Club, 5)],
+            &[card(Suit::Heart, 1), card(Suit::Heart, 2), card(Suit::Heart, 10)],
+        );
+        assert!(draws.find(DrawKind::NutFlushDraw).is_some());
+    }
+
+    #[test]
+    fn four_cards_of_suit_on_board_is_flush_draw_without_hole_cards() {
+        let draws = classify(
+            [card(Suit::Spade, 13), card(Suit::Club, 5)],
+            &[card(Suit::Heart, 1), card(Suit::Heart, 2), card(Suit::Heart, 10), card(Suit::Heart, 8)],
+        );
+        let draw = draws.find(DrawKind::FlushDraw).expect("flush draw is expected");
+        assert_eq!(0, draw.hole_cards_used());
+        assert!(!draw.uses_hole_cards());
+    }
+
+    #[test]
+    fn made_flush_is_not_flush_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 13), card(Suit::Heart, 5)],
+            &[card(Suit::Heart, 1), card(Suit::Heart, 2), card(Suit::Heart, 10)],
+        );
+        assert!(!draws.all().iter().any(|d| d.kind().is_flush_draw()));
+    }
+
+    #[test]
+    fn three_cards_of_suit_on_flop_is_backdoor_flush_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 13), card(Suit::Heart, 5)],
+            &[card(Suit::Heart, 1), card(Suit::Club, 2), card(Suit::Spade, 10)],
+        );
+        let draw = draws.find(DrawKind::BackdoorFlushDraw).expect("backdoor flush draw is expected");
+        assert_eq!(2, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn three_cards_of_suit_on_turn_is_not_backdoor_flush_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 13), card(Suit::Heart, 5)],
+            &[card(Suit::Heart, 1), card(Suit::Club, 2), card(Suit::Spade, 10), card(Suit::Club, 8)],
+        );
+        assert_eq!(None, draws.find(DrawKind::BackdoorFlushDraw));
+    }
+
+    #[test]
+    fn four_sequential_ranks_is_open_ended_straight_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 6), card(Suit::Club, 5)],
+            &[card(Suit::Spade, 4), card(Suit::Diamond, 3), card(Suit::Club, 13)],
+        );
+        let draw = draws.find(DrawKind::OpenEndedStraightDraw).expect("OESD is expected");
+        assert_eq!(2, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn two_to_five_is_open_ended_straight_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 2), card(Suit::Club, 3)],
+            &[card(Suit::Spade, 4), card(Suit::Diamond, 5), card(Suit::Club, 13)],
+        );
+        assert!(draws.find(DrawKind::OpenEndedStraightDraw).is_some());
+    }
+
+    #[test]
+    fn jack_to_ace_is_gutshot() {
+        let draws = classify(
+            [card(Suit::Heart, 1), card(Suit::Club, 13)],
+            &[card(Suit::Spade, 12), card(Suit::Diamond, 11), card(Suit::Club, 3)],
+        );
+        assert!(draws.find(DrawKind::Gutshot).is_some());
+        assert_eq!(None, draws.find(DrawKind::OpenEndedStraightDraw));
+    }
+
+    #[test]
+    fn ace_to_four_is_gutshot() {
+        let draws = classify(
+            [card(Suit::Heart, 1), card(Suit::Club, 2)],
+            &[card(Suit::Spade, 3), card(Suit::Diamond, 4), card(Suit::Club, 12)],
+        );
+        assert!(draws.find(DrawKind::Gutshot).is_some());
+    }
+
+    #[test]
+    fn one_missing_rank_inside_is_gutshot() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Club, 8)],
+            &[card(Suit::Spade, 6), card(Suit::Diamond, 5), card(Suit::Club, 13)],
+        );
+        let draw = draws.find(DrawKind::Gutshot).expect("gutshot is expected");
+        assert_eq!(2, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn two_inside_missing_ranks_is_double_gutshot() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Club, 7)],
+            &[card(Suit::Spade, 6), card(Suit::Diamond, 5), card(Suit::Club, 3)],
+        );
+        let draw = draws.find(DrawKind::DoubleGutshot).expect("double gutshot is expected");
+        assert_eq!(2, draw.hole_cards_used());
+        assert_eq!(None, draws.find(DrawKind::OpenEndedStraightDraw));
+    }
+
+    #[test]
+    fn open_ended_straight_draw_on_board_uses_no_hole_card() {
+        let draws = classify(
+            [card(Suit::Heart, 13), card(Suit::Club, 13)],
+            &[card(Suit::Spade, 9), card(Suit::Diamond, 8), card(Suit::Club, 7), card(Suit::Heart, 6)],
+        );
+        let draw = draws.find(DrawKind::OpenEndedStraightDraw).expect("OESD is expected");
+        assert_eq!(0, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn straight_draw_can_use_one_hole_card() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Club, 13)],
+            &[card(Suit::Spade, 8), card(Suit::Diamond, 7), card(Suit::Club, 6)],
+        );
+        let draw = draws.find(DrawKind::OpenEndedStraightDraw).expect("OESD is expected");
+        assert_eq!(1, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn made_straight_is_not_straight_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Club, 10)],
+            &[card(Suit::Spade, 8), card(Suit::Diamond, 7), card(Suit::Club, 6)],
+        );
+        assert!(!draws.all().iter().any(|d| d.kind().is_straight_draw() || d.kind().is_backdoor()));
+    }
+
+    #[test]
+    fn three_of_five_sequential_ranks_on_flop_is_backdoor_straight_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Club, 8)],
+            &[card(Suit::Spade, 6), card(Suit::Diamond, 2), card(Suit::Club, 13)],
+        );
+        let draw = draws.find(DrawKind::BackdoorStraightDraw).expect("backdoor straight draw is expected");
+        assert_eq!(2, draw.hole_cards_used());
+    }
+
+    #[test]
+    fn backdoor_straight_draw_is_not_reported_with_straight_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Club, 8)],
+            &[card(Suit::Spade, 7), card(Suit::Diamond, 6), card(Suit::Club, 13)],
+        );
+        assert_eq!(None, draws.find(DrawKind::BackdoorStraightDraw));
+    }
+
+    #[test]
+    fn flush_draw_and_straight_draw_is_combo_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Heart, 8)],
+            &[card(Suit::Heart, 7), card(Suit::Heart, 6), card(Suit::Club, 13)],
+        );
+        assert!(draws.is_combo_draw());
+    }
+
+    #[test]
+    fn flush_draw_only_is_not_combo_draw() {
+        let draws = classify(
+            [card(Suit::Heart, 9), card(Suit::Heart, 2)],
+            &[card(Suit::Heart, 12), card(Suit::Heart, 6), card(Suit::Club, 13)],
+        );
+        assert!(!draws.is_combo_draw());
+    }
+}
@@ -0,0 +1,236 @@
+use super::StartingHand;
+use playing_card::card::{ CardRank, NonJokerCard, Suit };
+use std::cmp::Ordering;
+use std::fmt;
+
+/// One of 169 strategically distinct starting hands, such as "AA", "AKs" and "AKo".
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct HandClass {
+    higher_rank: CardRank,
+    lower_rank: CardRank,
+    suited: bool,
+}
+
+/// Position of a rank in rows and columns of the hand grid, which are ranks in descending order.
+fn grid_index_of(rank: CardRank) -> usize {
+    if rank.is_ace() { 0 } else { 14 - rank.to_int() as usize }
+}
+
+fn rank_of_grid_index(index: usize) -> CardRank {
+    if index == 0 { CardRank::Ace } else { CardRank::new(14 - index as u8) }
+}
+
+impl HandClass {
+    pub const COUNT: usize = 169;
+
+    /// All 169 classes in order of index().
+    pub fn all() -> Vec<Self> {
+        (0 .. Self::COUNT).map(Self::from_index).collect()
+    }
+
+    pub fn of(hand: StartingHand) -> Self {
+        Self {
+            higher_rank: hand.higher_card().rank(),
+            lower_rank: hand.lower_card().rank(),
+            suited: hand.is_suited(),
+        }
+    }
+
+    /// Parses a summary such as "AA", "AKs" or "T9o".
+    pub fn from_summary(summary: &str) -> Option<Self> {
+        let chars: Vec<char> = summary.chars().collect();
+        let (a, b) = match chars.len() {
+            2 | 3 => (CardRank::from_char(chars[0])?, CardRank::from_char(chars[1])?),
+            _ => return None,
+        };
+        let suited = match (a == b, chars.get(2)) {
+            (true, None) => false,
+            (false, Some('s')) => true,
+            (false, Some('o')) => false,
+            _ => return None,
+        };
+        let (higher_rank, lower_rank) = if super::cmp_card_ranks(a, b) == Ordering::Less { (b, a) } else { (a, b) };
+        Some(Self { higher_rank, lower_rank, suited })
+    }
+
+    /// Dense index from 0 to 168 in row-major order of the 13x13 hand grid.
+    pub fn index(self) -> usize {
+        let (row, column) = self.grid_position();
+        row * 13 + column
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        assert!(index < Self::COUNT, "Index {} is out of range", index);
+        Self::from_grid_position(index / 13, index % 13)
+    }
+
+    /// Row and column on the 13x13 hand grid whose rows and columns are ranks from Ace to 2.
+    /// Pairs are on the diagonal, suited hands are above it and offsuit hands are below it.
+    pub fn grid_position(self) -> (usize, usize) {
+        let higher = grid_index_of(self.higher_rank);
+        let lower = grid_index_of(self.lower_rank);
+        if self.suited { (higher, lower) } else { (lower, higher) }
+    }
+
+    pub fn from_grid_position(row: usize, column: usize) -> Self {
+        Self {
+            higher_rank: rank_of_grid_index(row.min(column)),
+            lower_rank: rank_of_grid_index(row.max(column)),
+            suited: row < column,
+        }
+    }
+
+    pub fn higher_rank(self) -> CardRank {
+        self.higher_rank
+    }
+
+    pub fn lower_rank(self) -> CardRank {
+        self.lower_rank
+    }
+
+    pub fn is_pair(self) -> bool {
+        self.higher_rank == self.lower_rank
+    }
+
+    pub fn is_suited(self) -> bool {
+        self.suited
+    }
+
+    /// Number of starting hands in this class: 6 for pairs, 4 for suited and 12 for offsuit hands.
+    pub fn combo_count(self) -> usize {
+        if self.is_pair() {
+            6
+        } else if self.suited {
+            4
+        } else {
+            12
+        }
+    }
+
+    pub fn starting_hands(self) -> Vec<StartingHand> {
+        let suits = Suit::all();
+        let mut v = Vec::new();
+        for (i, a) in suits.iter().enumerate() {
+            for (j, b) in suits.iter().enumerate() {
+                let is_target = if self.is_pair() {
+                    i < j
+                } else if self.suited {
+                    i == j
+                } else {
+                    i != j
+                };
+                if is_target {
+                    v.push(StartingHand::new(
+                        NonJokerCard::new(*a, self.higher_rank),
+                        NonJokerCard::new(*b, self.lower_rank),
+                    ));
+                }
+            }
+        }
+        v
+    }
+
+    pub fn summary(self) -> String {
+        let suffix = if self.is_pair() {
+            ""
+        } else if self.suited {
+            "s"
+        } else {
+            "o"
+        };
+        format!("{}{}{}", self.higher_rank.to_char(), self.lower_rank.to_char(), suffix)
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary())
+    }
+}
+
+impl StartingHand {
+    pub fn class(self) -> HandClass {
+        HandClass::of(self)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn there_are_169_different_classes() {
+        let classes: HashSet<HandClass> = HandClass::all().into_iter().collect();
+        assert_eq!(169, classes.len());
+    }
+
+    #[test]
+    fn index_is_inverse_of_from_index() {
+        for i in 0 .. HandClass::COUNT {
+            assert_eq!(i, HandClass::from_index(i).index());
+        }
+    }
+
+    #[test]
+    fn summary_is_same_as_starting_hand() {
+        for hand in StartingHand::all() {
+            assert_eq!(hand.summary(), hand.class().summary());
+        }
+    }
+
+    #[test]
+    fn can_parse_summary() {
+        for class in HandClass::all() {
+            assert_eq!(Some(class), HandClass::from_summary(&class.summary()));
+        }
+    }
+
+    #[test]
+    fn summary_in_lower_rank_first_can_be_parsed() {
+        assert_eq!(HandClass::from_summary("AKs"), HandClass::from_summary("KAs"));
+    }
+
+    #[test]
+    fn invalid_summaries_are_not_parsed() {
+        assert_eq!(None, HandClass::from_summary("AAs"));
+        assert_eq!(None, HandClass::from_summary("AK"));
+        assert_eq!(None, HandClass::from_summary("AKx"));
+        assert_eq!(None, HandClass::from_summary("A1o"));
+        assert_eq!(None, HandClass::from_summary(""));
+    }
+
+    #[test]
+    fn combos_of_classes_are_1326_in_total() {
+        let total: usize = HandClass::all().iter().map(|c| c.combo_count()).sum();
+        assert_eq!(1326, total);
+    }
+
+    #[test]
+    fn starting_hands_of_class_have_the_class() {
+        for class in HandClass::all() {
+            let hands = class.starting_hands();
+            assert_eq!(class.combo_count(), hands.len());
+            assert!(hands.iter().all(|h| h.class() == class));
+        }
+    }
+
+    #[test]
+    fn pairs_are_on_diagonal_of_grid() {
+        let class = HandClass::from_summary("TT").unwrap();
+        assert_eq!((4, 4), class.grid_position());
+    }
+
+    #[test]
+    fn suited_hands_are_above_diagonal_of_grid() {
+        let class = HandClass::from_summary("AKs").unwrap();
+        assert_eq!((0, 1), class.grid_position());
+    }
+
+    #[test]
+    fn offsuit_hands_are_below_diagonal_of_grid() {
+        let class = HandClass::from_summary("AKo").unwrap();
+        assert_eq!((1, 0), class.grid_position());
+    }
+}
@@ -3,6 +3,7 @@ use playing_card::card::CardRank;
 use std::cmp;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HandValue {
     pub(super) hand_rank: HandRank,
     pub(super) card_ranks: [CardRank; 5],
@@ -0,0 +1,379 @@
+use super::{ BestFiveHand, Board, HandRank, StartingHand, TotalHand };
+use playing_card::card::{ CardRank, NonJokerCard };
+use std::cmp::Ordering;
+
+/// Strength of a made hand relative to the board.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum MadeHandCategory {
+    /// No pair or better is made.
+    HighCard,
+    /// The made part of the best hand consists of board cards only.
+    /// Hole cards work as kickers at most.
+    BoardPlays,
+    /// Pocket pair higher than any board card.
+    Overpair,
+    /// Hole card paired with the highest board card.
+    TopPair,
+    /// Hole card paired with a board card between the highest and the lowest,
+    /// or pocket pair between the highest and the lowest board cards.
+    MiddlePair,
+    /// Hole card paired with the lowest board card.
+    BottomPair,
+    /// Pocket pair lower than any board card.
+    Underpair,
+    TwoPairUsingBothHoleCards,
+    /// One pair is made with a hole card and the other pair is on the board.
+    TwoPairUsingOneHoleCard,
+    /// Three of a kind with a pocket pair.
+    Set,
+    /// Three of a kind with one hole card and a pair on the board.
+    Trips,
+    /// Straight or higher hand rank using hole cards. See hand_rank() for its detail.
+    StraightOrBetter,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum KickerQuality {
+    Weak,
+    Good,
+    /// The best kicker any player could have.
+    Top,
+}
+
+// Kickers up to this position among the available ranks are regarded as good.
+const GOOD_KICKER_POSITION: usize = 2;
+
+/// Made hand of a starting hand classified relative to the board.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MadeHand {
+    starting_hand: StartingHand,
+    board_cards: Vec<NonJokerCard>,
+    best_five_hand: BestFiveHand,
+    category: MadeHandCategory,
+    kicker: Option<CardRank>,
+}
+
+impl MadeHand {
+    /// Board must have 3 or more cards.
+    pub fn classify(hand: StartingHand, board: &Board) -> Self {
+        let board_cards = board.cards();
+        assert!(board_cards.len() >= 3, "Made hand cannot be classified before flop");
+        let best_five_hand = TotalHand::new_from_starting_hand_and_board(hand, board)
+                                 .find_best_five_hand()
+                                 .expect("best five hand not found with 5 or more cards");
+
+        let made_cards = &best_five_hand.cards()[.. made_card_count(best_five_hand.hand_rank())];
+        let hole_cards_in_made_part: Vec<NonJokerCard> = hand.both_cards().iter()
+                                                             .filter(|c| made_cards.contains(c))
+                                                             .copied()
+                                                             .collect();
+        let category = categorize(best_five_hand, &hole_cards_in_made_part, &board_cards);
+        let kicker = hand.both_cards().iter()
+                         .find(|c| !hole_cards_in_made_part.contains(c))
+                         .map(|c| c.rank());
+
+        Self {
+            starting_hand: hand,
+            board_cards,
+            best_five_hand,
+            category,
+            kicker,
+        }
+    }
+
+    pub fn category(&self) -> MadeHandCategory {
+        self.category
+    }
+
+    pub fn hand_rank(&self) -> HandRank {
+        self.best_five_hand.hand_rank()
+    }
+
+    pub fn best_five_hand(&self) -> BestFiveHand {
+        self.best_five_hand
+    }
+
+    /// The highest hole card which is not a part of the made hand.
+    pub fn kicker(&self) -> Option<CardRank> {
+        self.kicker
+    }
+
+    /// Quality of the kicker compared with ranks other players could have as a kicker.
+    pub fn kicker_quality(&self) -> Option<KickerQuality> {
+        let kicker = self.kicker?;
+        let made_cards = &self.best_five_hand.cards()[.. made_card_count(self.hand_rank())];
+        let mut available_ranks: Vec<CardRank> = CardRank::all().iter()
+            .filter(|r| !self.board_cards.iter().any(|c| c.rank() == **r))
+            .filter(|r| !made_cards.iter().any(|c| c.rank() == **r))
+            .copied()
+            .collect();
+        available_ranks.sort_by(|a, b| super::cmp_card_ranks(*a, *b).reverse());
+        let position = available_ranks.iter().position(|r| *r == kicker)?;
+        let quality = match position {
+            0 => KickerQuality::Top,
+            p if p <= GOOD_KICKER_POSITION => KickerQuality::Good,
+            _ => KickerQuality::Weak,
+        };
+        Some(quality)
+    }
+
+    /// Number of hole cards higher than any board card.
+    pub fn overcard_count(&self) -> usize {
+        self.starting_hand.both_cards().iter()
+            .filter(|h| self.board_cards.iter().all(|c| super::cmp_card_ranks(h.rank(), c.rank()) == Ordering::Greater))
+            .count()
+    }
+
+    /// Returns true if no other starting hand can make a better hand on this board.
+    /// It evaluates all possible hands of opponents on each call.
+    pub fn is_nuts(&self) -> bool {
+        let board = Board::new_with_cards(&self.board_cards);
+        let opponent_hands = super::nuts::rank_hands_on_board(&board, &self.starting_hand.both_cards());
+        opponent_hands.first().is_none_or(|best| best.value() <= self.best_five_hand.value())
+    }
+}
+
+/// Number of cards that make the hand rank. Others are kickers.
+fn made_card_count(hand_rank: HandRank) -> usize {
+    match hand_rank {
+        HandRank::HighCard => 0,
+        HandRank::Pair => 2,
+        HandRank::ThreeOfAKind => 3,
+        HandRank::TwoPairs | HandRank::FourOfAKind => 4,
+        _ => 5,
+    }
+}
+
+fn categorize(best_five_hand: BestFiveHand, hole_cards_in_made_part: &[NonJokerCard], board_cards: &[NonJokerCard]) -> MadeHandCategory {
+    let hand_rank = best_five_hand.hand_rank();
+    if hand_rank == HandRank::HighCard {
+        return MadeHandCategory::HighCard;
+    }
+    if hole_cards_in_made_part.is_empty() {
+        return MadeHandCategory::BoardPlays;
+    }
+    match (hand_rank, hole_cards_in_made_part.len()) {
+        (HandRank::Pair, 1) => categorize_paired_board_card(hole_cards_in_made_part[0].rank(), board_cards),
+        (HandRank::Pair, _) => categorize_pocket_pair(hole_cards_in_made_part[0].rank(), board_cards),
+        (HandRank::TwoPairs, 1) => MadeHandCategory::TwoPairUsingOneHoleCard,
+        // a pocket pair with a pair on the board is ranked against every board card, including the paired ones
+        (HandRank::TwoPairs, _) if hole_cards_in_made_part[0].rank() == hole_cards_in_made_part[1].rank() => {
+            categorize_pocket_pair(hole_cards_in_made_part[0].rank(), board_cards)
+        },
+        (HandRank::TwoPairs, _) => MadeHandCategory::TwoPairUsingBothHoleCards,
+        (HandRank::ThreeOfAKind, 1) => MadeHandCategory::Trips,
+        (HandRank::ThreeOfAKind, _) => MadeHandCategory::Set,
+        _ => MadeHandCategory::StraightOrBetter,
+    }
+}
+
+fn categorize_paired_board_card(rank: CardRank, board_cards: &[NonJokerCard]) -> MadeHandCategory {
+    let (highest, lowest) = highest_and_lowest_ranks(board_cards);
+    if rank == highest {
+        MadeHandCategory::TopPair
+    } else if rank == lowest {
+        MadeHandCategory::BottomPair
+    } else {
+        MadeHandCategory::MiddlePair
+    }
+}
+
+fn categorize_pocket_pair(rank: CardRank, board_cards: &[NonJokerCard]) -> MadeHandCategory {
+    let (highest, lowest) = highest_and_lowest_ranks(board_cards);
+    if super::cmp_card_ranks(rank, highest) == Ordering::Greater {
+        MadeHandCategory::Overpair
+    } else if super::cmp_card_ranks(rank, lowest) == Ordering::Less {
+        MadeHandCategory::Underpair
+    } else {
+        MadeHandCategory::MiddlePair
+    }
+}
+
+fn highest_and_lowest_ranks(board_cards: &[NonJokerCard]) -> (CardRank, CardRank) {
+    let highest = board_cards.iter().map(|c| c.rank()).max_by(|a, b| super::cmp_card_ranks(*a, *b)).unwrap();
+    let lowest = board_cards.iter().map(|c| c.rank()).min_by(|a, b| super::cmp_card_ranks(*a, *b)).unwrap();
+    (highest, lowest)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::Suit;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn classify(hole: [NonJokerCard; 2], board_cards: &[NonJokerCard]) -> MadeHand {
+        let hand = StartingHand::new(hole[0], hole[1]);
+        let board = Board::new_with_cards(board_cards);
+        MadeHand::classify(hand, &board)
+    }
+
+    fn dry_flop() -> [NonJokerCard; 3] {
+        [card(Suit::Spade, 13), card(Suit::Diamond, 8), card(Suit::Club, 3)]
+    }
+
+    #[test]
+    #[should_panic(expected = "before flop")]
+    fn should_panic_on_pre_flop() {
+        classify([card(Suit::Heart, 1), card(Suit::Heart, 13)], &[]);
+    }
+
+    #[test]
+    fn no_pair_is_high_card() {
+        let made_hand = classify([card(Suit::Heart, 1), card(Suit::Heart, 12)], &dry_flop());
+        assert_eq!(MadeHandCategory::HighCard, made_hand.category());
+    }
+
+    #[test]
+    fn pocket_pair_higher_than_board_is_overpair() {
+        let made_hand = classify([card(Suit::Heart, 1), card(Suit::Club, 1)], &dry_flop());
+        assert_eq!(MadeHandCategory::Overpair, made_hand.category());
+        assert_eq!(None, made_hand.kicker());
+    }
+
+    #[test]
+    fn pocket_pair_lower_than_board_is_underpair() {
+        let made_hand = classify([card(Suit::Heart, 2), card(Suit::Club, 2)], &dry_flop());
+        assert_eq!(MadeHandCategory::Underpair, made_hand.category());
+    }
+
+    #[test]
+    fn pocket_pair_between_board_cards_is_middle_pair() {
+        let made_hand = classify([card(Suit::Heart, 10), card(Suit::Club, 10)], &dry_flop());
+        assert_eq!(MadeHandCategory::MiddlePair, made_hand.category());
+    }
+
+    #[test]
+    fn pocket_pair_on_paired_board_is_ranked_against_every_board_card() {
+        let queens = classify([card(Suit::Heart, 12), card(Suit::Club, 12)], &[card(Suit::Spade, 13), card(Suit::Diamond, 13), card(Suit::Club, 3)]);
+        assert_eq!(MadeHandCategory::MiddlePair, queens.category());
+        let aces = classify([card(Suit::Heart, 1), card(Suit::Club, 1)], &[card(Suit::Spade, 7), card(Suit::Diamond, 7), card(Suit::Club, 2)]);
+        assert_eq!(MadeHandCategory::Overpair, aces.category());
+        let fives = classify([card(Suit::Heart, 5), card(Suit::Club, 5)], &[card(Suit::Spade, 7), card(Suit::Diamond, 7), card(Suit::Club, 9)]);
+        assert_eq!(MadeHandCategory::Underpair, fives.category());
+    }
+
+    #[test]
+    fn pair_with_highest_board_card_is_top_pair() {
+        let made_hand = classify([card(Suit::Heart, 13), card(Suit::Club, 1)], &dry_flop());
+        assert_eq!(MadeHandCategory::TopPair, made_hand.category());
+        assert_eq!(Some(CardRank::Ace), made_hand.kicker());
+        assert_eq!(Some(KickerQuality::Top), made_hand.kicker_quality());
+    }
+
+    #[test]
+    fn pair_with_middle_board_card_is_middle_pair() {
+        let made_hand = classify([card(Suit::Heart, 8), card(Suit::Club, 1)], &dry_flop());
+        assert_eq!(MadeHandCategory::MiddlePair, made_hand.category());
+    }
+
+    #[test]
+    fn pair_with_lowest_board_card_is_bottom_pair() {
+        let made_hand = classify([card(Suit::Heart, 3), card(Suit::Club, 1)], &dry_flop());
+        assert_eq!(MadeHandCategory::BottomPair, made_hand.category());
+    }
+
+    #[test]
+    fn second_best_kicker_is_good() {
+        let made_hand = classify([card(Suit::Heart, 13), card(Suit::Club, 12)], &dry_flop());
+        assert_eq!(Some(KickerQuality::Good), made_hand.kicker_quality());
+    }
+
+    #[test]
+    fn low_kicker_is_weak() {
+        let made_hand = classify([card(Suit::Heart, 13), card(Suit::Club, 4)], &dry_flop());
+        assert_eq!(Some(KickerQuality::Weak), made_hand.kicker_quality());
+    }
+
+    #[test]
+    fn king_is_top_kicker_when_ace_is_on_board() {
+        let board_cards = [card(Suit::Spade, 1), card(Suit::Diamond, 8), card(Suit::Club, 3)];
+        let made_hand = classify([card(Suit::Heart, 1), card(Suit::Club, 13)], &board_cards);
+        assert_eq!(Some(KickerQuality::Top), made_hand.kicker_quality());
+    }
+
+    #[test]
+    fn pair_on_board_only_is_board_plays() {
+        let board_cards = [card(Suit::Spade, 13), card(Suit::Diamond, 13), card(Suit::Club, 3)];
+        let made_hand = classify([card(Suit::Heart, 1), card(Suit::Club, 12)], &board_cards);
+        assert_eq!(MadeHandCategory::BoardPlays, made_hand.category());
+        assert_eq!(Some(CardRank::Ace), made_hand.kicker());
+    }
+
+    #[test]
+    fn two_pair_with_both_hole_cards() {
+        let made_hand = classify([card(Suit::Heart, 13), card(Suit::Club, 8)], &dry_flop());
+        assert_eq!(MadeHandCategory::TwoPairUsingBothHoleCards, made_hand.category());
+    }
+
+    #[test]
+    fn two_pair_with_one_hole_card_and_board_pair() {
+        let board_cards = [card(Suit::Spade, 13), card(Suit::Diamond, 13), card(Suit::Club, 3)];
+        let made_hand = classify([card(Suit::Heart, 3), card(Suit::Club, 1)], &board_cards);
+        assert_eq!(MadeHandCategory::TwoPairUsingOneHoleCard, made_hand.category());
+    }
+
+    #[test]
+    fn three_of_a_kind_with_pocket_pair_is_set() {
+        let made_hand = classify([card(Suit::Heart, 8), card(Suit::Club, 8)], &dry_flop());
+        assert_eq!(MadeHandCategory::Set, made_hand.category());
+    }
+
+    #[test]
+    fn three_of_a_kind_with_board_pair_is_trips() {
+        let board_cards = [card(Suit::Spade, 13), card(Suit::Diamond, 13), card(Suit::Club, 3)];
+        let made_hand = classify([card(Suit::Heart, 13), card(Suit::Club, 1)], &board_cards);
+        assert_eq!(MadeHandCategory::Trips, made_hand.category());
+        assert_eq!(Some(KickerQuality::Top), made_hand.kicker_quality());
+    }
+
+    #[test]
+    fn straight_using_hole_cards_is_straight_or_better() {
+        let board_cards = [card(Suit::Spade, 9), card(Suit::Diamond, 8), card(Suit::Club, 7)];
+        let made_hand = classify([card(Suit::Heart, 10), card(Suit::Club, 6)], &board_cards);
+        assert_eq!(MadeHandCategory::StraightOrBetter, made_hand.category());
+        assert_eq!(HandRank::Straight, made_hand.hand_rank());
+    }
+
+    #[test]
+    fn straight_on_board_is_board_plays() {
+        let board_cards = [
+            card(Suit::Spade, 9),
+            card(Suit::Diamond, 8),
+            card(Suit::Club, 7),
+            card(Suit::Club, 6),
+            card(Suit::Heart, 5),
+        ];
+        let made_hand = classify([card(Suit::Heart, 2), card(Suit::Club, 2)], &board_cards);
+        assert_eq!(MadeHandCategory::BoardPlays, made_hand.category());
+    }
+
+    #[test]
+    fn overcards_are_counted() {
+        let made_hand = classify([card(Suit::Heart, 1), card(Suit::Club, 12)], &dry_flop());
+        assert_eq!(1, made_hand.overcard_count());
+    }
+
+    #[test]
+    fn top_set_is_nuts_on_dry_board() {
+        let board_cards = [card(Suit::Spade, 9), card(Suit::Diamond, 8), card(Suit::Club, 2)];
+        let made_hand = classify([card(Suit::Heart, 9), card(Suit::Club, 9)], &board_cards);
+        assert!(made_hand.is_nuts());
+    }
+
+    #[test]
+    fn second_set_is_not_nuts() {
+        let board_cards = [card(Suit::Spade, 9), card(Suit::Diamond, 8), card(Suit::Club, 2)];
+        let made_hand = classify([card(Suit::Heart, 8), card(Suit::Club, 8)], &board_cards);
+        assert!(!made_hand.is_nuts());
+    }
+
+    #[test]
+    fn top_pair_is_not_nuts() {
+        let made_hand = classify([card(Suit::Heart, 13), card(Suit::Club, 1)], &dry_flop());
+        assert!(!made_hand.is_nuts());
+    }
+}
@@ -0,0 +1,120 @@
+use super::{ Board, HandValue, StartingHand, TotalHand };
+use playing_card::card::NonJokerCard;
+use std::collections::HashMap;
+
+/// A hand value some players could make on a board, and the starting hands making it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NutHand {
+    value: HandValue,
+    starting_hands: Vec<StartingHand>,
+}
+
+impl NutHand {
+    pub fn value(&self) -> HandValue {
+        self.value
+    }
+
+    /// Starting hands that make this hand value.
+    pub fn starting_hands(&self) -> &[StartingHand] {
+        &self.starting_hands
+    }
+}
+
+impl Board {
+    /// Returns the best possible hand on this board.
+    /// Board must have 3 or more cards.
+    pub fn nuts(&self) -> NutHand {
+        self.nut_hands(1).remove(0)
+    }
+
+    /// Returns hands of the given number in descending order,
+    /// i.e. the nuts, the second nuts, the third nuts and so on.
+    /// Board must have 3 or more cards.
+    pub fn nut_hands(&self, depth: usize) -> Vec<NutHand> {
+        let mut nut_hands = rank_hands_on_board(self, &[]);
+        nut_hands.truncate(depth);
+        nut_hands
+    }
+}
+
+/// Groups all starting hands without given dead cards by their best hand values.
+/// Higher hand value comes first.
+pub(super) fn rank_hands_on_board(board: &Board, dead_cards: &[NonJokerCard]) -> Vec<NutHand> {
+    let board_cards = board.cards();
+    assert!(board_cards.len() >= 3, "Nuts cannot be calculated before flop");
+
+    let mut hands_by_value: HashMap<HandValue, Vec<StartingHand>> = HashMap::new();
+    for hand in StartingHand::combos_without(&[&board_cards[..], dead_cards].concat()) {
+        let best = TotalHand::new_from_starting_hand_and_board(hand, board)
+                       .find_best_five_hand()
+                       .expect("best five hand not found with 5 or more cards");
+        hands_by_value.entry(best.value()).or_default().push(hand);
+    }
+
+    let mut nut_hands: Vec<NutHand> = hands_by_value.into_iter()
+        .map(|(value, starting_hands)| NutHand { value, starting_hands })
+        .collect();
+    nut_hands.sort_by(|a, b| a.value.cmp(&b.value).reverse());
+    nut_hands
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::HandRank;
+    use playing_card::card::{ CardRank, Suit };
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    #[test]
+    #[should_panic(expected = "before flop")]
+    fn should_panic_on_pre_flop() {
+        Board::new().nuts();
+    }
+
+    #[test]
+    fn nuts_is_royal_flush_when_possible() {
+        let board = Board::new_with_cards(&[card(Suit::Heart, 1), card(Suit::Heart, 13), card(Suit::Heart, 12)]);
+        let nuts = board.nuts();
+        assert_eq!(HandRank::RoyalFlush, nuts.value().hand_rank);
+        let expected = [StartingHand::new(card(Suit::Heart, 11), card(Suit::Heart, 10))];
+        assert_eq!(&expected, nuts.starting_hands());
+    }
+
+    #[test]
+    fn second_and_third_nuts_are_found() {
+        let board = Board::new_with_cards(&[card(Suit::Heart, 1), card(Suit::Heart, 13), card(Suit::Heart, 12)]);
+        let nut_hands = board.nut_hands(3);
+        assert_eq!(3, nut_hands.len());
+        assert_eq!(HandRank::Flush, nut_hands[1].value().hand_rank);
+        assert_eq!(&[StartingHand::new(card(Suit::Heart, 11), card(Suit::Heart, 9))], nut_hands[1].starting_hands());
+        assert_eq!(HandRank::Flush, nut_hands[2].value().hand_rank);
+        assert_eq!(&[StartingHand::new(card(Suit::Heart, 11), card(Suit::Heart, 8))], nut_hands[2].starting_hands());
+    }
+
+    #[test]
+    fn top_set_is_nuts_on_dry_board() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Club, 2),
+            card(Suit::Diamond, 7),
+            card(Suit::Spade, 13),
+            card(Suit::Heart, 9),
+            card(Suit::Club, 4),
+        ]);
+        let nut_hands = board.nut_hands(3);
+        let ranks: Vec<CardRank> = nut_hands.iter().map(|n| n.value().card_ranks[0]).collect();
+        assert_eq!(vec![CardRank::King, CardRank::new(9), CardRank::new(7)], ranks);
+        assert!(nut_hands.iter().all(|n| n.value().hand_rank == HandRank::ThreeOfAKind));
+        assert!(nut_hands.iter().all(|n| n.starting_hands().len() == 3));
+    }
+
+    #[test]
+    fn all_hands_are_ranked() {
+        let board = Board::new_with_cards(&[card(Suit::Club, 2), card(Suit::Diamond, 7), card(Suit::Spade, 13)]);
+        let count: usize = rank_hands_on_board(&board, &[]).iter().map(|n| n.starting_hands().len()).sum();
+        assert_eq!(1176, count);
+    }
+}
@@ -12,6 +12,7 @@ mod straight_flush;
 mod royal_flush;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
 pub enum HandRank {
     HighCard,
     Pair,
@@ -25,6 +26,23 @@ pub enum HandRank {
     RoyalFlush,
 }
 
+impl HandRank {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::HighCard => "High card",
+            Self::Pair => "Pair",
+            Self::TwoPairs => "Two pair",
+            Self::ThreeOfAKind => "Three of a kind",
+            Self::Straight => "Straight",
+            Self::Flush => "Flush",
+            Self::FullHouse => "Full house",
+            Self::FourOfAKind => "Four of a kind",
+            Self::StraightFlush => "Straight flush",
+            Self::RoyalFlush => "Royal flush",
+        }
+    }
+}
+
 impl TotalHand {
     pub fn find_best_five_hand(&self) -> Option<BestFiveHand> {
         royal_flush::try_to_build_from_total_hand(self)
@@ -38,7 +38,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -54,7 +54,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -71,7 +71,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -88,7 +88,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
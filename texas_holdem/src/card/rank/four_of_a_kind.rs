@@ -11,7 +11,7 @@ pub(super) fn try_to_build_from_total_hand(hand: &TotalHand) -> Option<BestFiveH
     let cards_of_quads: Vec<&NonJokerCard> = hand.cards().iter().filter(|c| c.rank() == rank).collect();
     let non_quad_cards: Vec<&NonJokerCard> =  hand.cards().iter().filter(|c| c.rank() != rank).collect();
     assert_eq!(4, cards_of_quads.len());
-    assert!(non_quad_cards.len() >= 1);
+    assert!(!non_quad_cards.is_empty());
 
     let cards = [
         *cards_of_quads[0],
@@ -39,7 +39,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -55,7 +55,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -71,7 +71,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
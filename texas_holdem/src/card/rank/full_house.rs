@@ -14,7 +14,7 @@ pub(super) fn try_to_build_from_total_hand(hand: &TotalHand) -> Option<BestFiveH
 
 
     let rank_of_set = ranks_of_sets[0];
-    let rank_of_pair = *ranks_of_sets.get(1).or(ranks_of_pairs.get(0)).unwrap();
+    let rank_of_pair = *ranks_of_sets.get(1).or(ranks_of_pairs.first()).unwrap();
     let cards_of_set: Vec<&NonJokerCard> = hand.cards().iter().filter(|c| c.rank() == rank_of_set).collect();
     let cards_of_pair: Vec<&NonJokerCard> = hand.cards().iter().filter(|c| c.rank() == rank_of_pair).collect();
     assert_eq!(3, cards_of_set.len());
@@ -47,7 +47,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -63,7 +63,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -80,7 +80,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -97,7 +97,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -31,7 +31,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -47,7 +47,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -18,8 +18,8 @@ pub(super) fn try_to_build_from_total_hand(hand: &TotalHand) -> Option<BestFiveH
     ];
 
     let card_vec: Vec<NonJokerCard> = ranks.iter().map(|r| {
-        *hand.cards().iter().filter(|c| c.rank() == *r).next()
-             .expect(&format!("a card of rank {} is not found for straight", r.to_int()))
+        *hand.cards().iter().find(|c| c.rank() == *r)
+             .unwrap_or_else(|| panic!("a card of rank {} is not found for straight", r.to_int()))
     }).collect();
 
     assert_eq!(5, card_vec.len());
@@ -51,7 +51,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -67,7 +67,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -83,7 +83,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -99,7 +99,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
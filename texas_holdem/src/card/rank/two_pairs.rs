@@ -12,8 +12,8 @@ pub(super) fn try_to_build_from_total_hand(hand: &TotalHand) -> Option<BestFiveH
     let cards_of_higher_pair: Vec<&NonJokerCard> = hand.cards().iter().filter(|c| c.rank() == higher_rank).collect();
     let cards_of_lower_pair: Vec<&NonJokerCard> = hand.cards().iter().filter(|c| c.rank() == lower_rank).collect();
     let non_pair_card = hand.cards().iter()
-                            .filter(|c| c.rank() != higher_rank && c.rank() != lower_rank)
-                            .next().expect("kicker of two pairs not found");
+                            .find(|c| c.rank() != higher_rank && c.rank() != lower_rank)
+                            .expect("kicker of two pairs not found");
     assert_eq!(2, cards_of_higher_pair.len());
     assert_eq!(2, cards_of_lower_pair.len());
 
@@ -43,7 +43,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -59,7 +59,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -75,7 +75,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let Some(_) = result {
+        if result.is_some() {
             panic!("Result is expected to be None");
         }
     }
@@ -91,7 +91,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -108,7 +108,7 @@ mod test {
         ];
         let hand = TotalHand::new(&given_cards);
         let result = try_to_build_from_total_hand(&hand);
-        if let None = result {
+        if result.is_none() {
             panic!("Result is expected not to be None");
         }
     }
@@ -1,7 +1,10 @@
+use crate::error::HoldemError;
 use playing_card::card::CardRank;
 use playing_card::card::NonJokerCard;
 use playing_card::card::Suit;
+use playing_card::combination::Combinations;
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
@@ -16,6 +19,23 @@ impl StartingHand {
         }
     }
 
+    /// Returns an error if the two cards are the same.
+    pub fn try_new(a: NonJokerCard, b: NonJokerCard) -> Result<Self, HoldemError> {
+        super::check_no_duplicates(&[a, b])?;
+        Ok(Self::new(a, b))
+    }
+
+    /// All 1326 combinations of two cards.
+    pub fn all() -> Vec<Self> {
+        Self::combos_without(&[]).collect()
+    }
+
+    /// All combinations of two cards without given dead cards, in the same order as all().
+    pub fn combos_without(dead_cards: &[NonJokerCard]) -> StartingHandCombos {
+        let live_cards = NonJokerCard::all().into_iter().filter(|c| !dead_cards.contains(c)).collect();
+        StartingHandCombos(Combinations::new(live_cards, 2))
+    }
+
     pub fn higher_card(self) -> NonJokerCard {
         self.0
     }
@@ -86,7 +106,7 @@ impl StartingHand {
         } else {
             "o"
         };
-        String::from(format!("{}{}{}", self.0.rank().to_char(), self.1.rank().to_char(), suffix))
+        format!("{}{}{}", self.0.rank().to_char(), self.1.rank().to_char(), suffix)
     }
 }
 
@@ -96,10 +116,60 @@ impl fmt::Display for StartingHand {
     }
 }
 
+impl TryFrom<[NonJokerCard; 2]> for StartingHand {
+    type Error = HoldemError;
+
+    fn try_from(cards: [NonJokerCard; 2]) -> Result<Self, Self::Error> {
+        Self::try_new(cards[0], cards[1])
+    }
+}
+
+/// Iterator over starting hands without some dead cards, which reports its exact length
+/// and can be split into iterators over consecutive parts.
+#[derive(Debug, Clone)]
+pub struct StartingHandCombos(Combinations<'static, NonJokerCard>);
+
+impl StartingHandCombos {
+    /// Splits into an iterator over the first given number of hands and one over the rest.
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let (first, rest) = self.0.split_at(mid);
+        (Self(first), Self(rest))
+    }
+
+    /// Splits into given number of iterators whose lengths differ by 1 at most.
+    pub fn split(self, parts: usize) -> Vec<Self> {
+        self.0.split(parts).into_iter().map(Self).collect()
+    }
+}
+
+impl Iterator for StartingHandCombos {
+    type Item = StartingHand;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_array().map(|[a, b]| StartingHand::new(a, b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for StartingHandCombos {}
+
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use playing_card::error::CardError;
+    use std::collections::HashSet;
+
+    #[test]
+    fn same_cards_are_not_a_starting_hand() {
+        let ace = NonJokerCard::new(Suit::Spade, CardRank::Ace);
+        let king = NonJokerCard::new(Suit::Spade, CardRank::King);
+        assert_eq!(Err(HoldemError::Card(CardError::DuplicateCard(ace))), StartingHand::try_new(ace, ace));
+        assert_eq!(Ok(StartingHand::new(ace, king)), StartingHand::try_from([king, ace]));
+    }
 
     #[test]
     fn higher_card_is_first_item_when_first_item_is_higher_than_second() {
@@ -470,4 +540,41 @@ mod test {
         let hand = StartingHand::new(a, b);
         assert_eq!("AKo", hand.summary())
     }
+
+    #[test]
+    fn all_starting_hands_are_1326_combinations() {
+        assert_eq!(1326, StartingHand::all().len());
+    }
+
+    #[test]
+    fn no_hand_is_left_with_a_live_card() {
+        let dead_cards = &NonJokerCard::all()[.. 51];
+        assert_eq!(None, StartingHand::combos_without(dead_cards).next());
+    }
+
+    #[test]
+    fn all_starting_hands_are_all_different() {
+        let hands = StartingHand::all();
+        let hand_count = hands.len();
+        let unique_set: HashSet<StartingHand> = hands.into_iter().collect();
+        assert_eq!(hand_count, unique_set.len());
+    }
+
+    #[test]
+    fn all_starting_hands_have_169_summaries() {
+        let summaries: HashSet<String> = StartingHand::all().into_iter().map(|h| h.summary()).collect();
+        assert_eq!(169, summaries.len());
+    }
+
+    #[test]
+    fn combos_without_dead_cards_keep_order_of_all() {
+        let dead = [NonJokerCard::new(Suit::Heart, CardRank::Ace), NonJokerCard::new(Suit::Club, CardRank::new(7))];
+        let combos = StartingHand::combos_without(&dead);
+        assert_eq!(50 * 49 / 2, combos.len());
+        let expected: Vec<StartingHand> = StartingHand::all().into_iter()
+            .filter(|h| !dead.contains(&h.higher_card()) && !dead.contains(&h.lower_card()))
+            .collect();
+        let split: Vec<StartingHand> = combos.split(3).into_iter().flatten().collect();
+        assert_eq!(expected, split);
+    }
 }
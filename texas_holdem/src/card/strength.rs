@@ -0,0 +1,202 @@
+use super::{ BestFiveHand, HandValue, TotalHand };
+use playing_card::card::{ CardRank, NonJokerCard, Suit };
+use std::sync::OnceLock;
+
+/// Number of distinct values of five-card hands.
+pub const DISTINCT_HAND_VALUE_COUNT: usize = 7462;
+
+/// Number of five-card hands dealt from a deck of 52 cards.
+const FIVE_CARD_HAND_COUNT: u32 = 2_598_960;
+
+/// Every distinct hand value with a representative hand and the number of five-card hands of the value.
+struct StrengthTable {
+    /// From the best to the worst.
+    hands: Vec<BestFiveHand>,
+    /// Number of five-card hands weaker than each value, and of the value itself.
+    weaker_and_equal_counts: Vec<(u32, u32)>,
+}
+
+fn strength_table() -> &'static StrengthTable {
+    static TABLE: OnceLock<StrengthTable> = OnceLock::new();
+    TABLE.get_or_init(StrengthTable::build)
+}
+
+impl StrengthTable {
+    fn build() -> Self {
+        let ranks = CardRank::all();
+        let best_of = |cards: &[NonJokerCard]| -> BestFiveHand {
+            TotalHand::new(cards).find_best_five_hand().expect("best five hand not found with 5 cards")
+        };
+        let mut hands_and_counts = Vec::with_capacity(DISTINCT_HAND_VALUE_COUNT);
+        // every multiset of 5 ranks with 4 cards at most of each rank
+        for a in 0 .. 13 {
+            for b in a .. 13 {
+                for c in b .. 13 {
+                    for d in c .. 13 {
+                        for e in d .. 13 {
+                            if a == e {
+                                continue;
+                            }
+                            let indices = [a, b, c, d, e];
+                            // the n-th card of a rank takes the n-th suit
+                            let mut cards: Vec<NonJokerCard> = indices.iter().enumerate()
+                                .map(|(n, i)| {
+                                    let order = indices[.. n].iter().filter(|j| *j == i).count();
+                                    NonJokerCard::new(Suit::all()[order], ranks[*i])
+                                })
+                                .collect();
+                            let suit_patterns: u32 = (0 .. 13)
+                                .map(|r| binomial(4, indices.iter().filter(|i| **i == r).count() as u32))
+                                .product();
+                            if a < b && b < c && c < d && d < e {
+                                // the flush of the same ranks, and the other suit patterns
+                                hands_and_counts.push((best_of(&cards), 4));
+                                cards[4] = NonJokerCard::new(Suit::Diamond, ranks[e]);
+                                hands_and_counts.push((best_of(&cards), suit_patterns - 4));
+                            } else {
+                                hands_and_counts.push((best_of(&cards), suit_patterns));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        hands_and_counts.sort_by_key(|(h, _)| std::cmp::Reverse(h.value()));
+        assert_eq!(DISTINCT_HAND_VALUE_COUNT, hands_and_counts.len(), "Hand values are not distinct");
+
+        let mut weaker_and_equal_counts = vec![(0, 0); hands_and_counts.len()];
+        let mut weaker = 0;
+        for (i, (_, count)) in hands_and_counts.iter().enumerate().rev() {
+            weaker_and_equal_counts[i] = (weaker, *count);
+            weaker += count;
+        }
+        assert_eq!(FIVE_CARD_HAND_COUNT, weaker, "Five-card hands are not counted correctly");
+        let hands = hands_and_counts.into_iter().map(|(h, _)| h).collect();
+        Self { hands, weaker_and_equal_counts }
+    }
+
+    fn position(&self, value: HandValue) -> usize {
+        self.hands.binary_search_by(|h| value.cmp(&h.value()))
+            .unwrap_or_else(|_| panic!("{:?} is not a value of five-card hands", value))
+    }
+}
+
+fn binomial(n: u32, k: u32) -> u32 {
+    (0 .. k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+impl HandValue {
+    /// Index of the value among the 7462 distinct values of five-card hands,
+    /// where 1 is a royal flush and 7462 is the worst high card.
+    pub fn strength_index(self) -> usize {
+        strength_table().position(self) + 1
+    }
+
+    /// The value of given strength index from 1 to 7462.
+    pub fn from_strength_index(index: usize) -> Self {
+        BestFiveHand::from_strength_index(index).value()
+    }
+
+    /// Share of all 2,598,960 five-card hands which the value beats. Hands of the same value count as half.
+    pub fn percentile(self) -> f64 {
+        let (weaker, equal) = strength_table().weaker_and_equal_counts[strength_table().position(self)];
+        (weaker as f64 + equal as f64 / 2.0) / FIVE_CARD_HAND_COUNT as f64
+    }
+}
+
+impl BestFiveHand {
+    /// See HandValue::strength_index().
+    pub fn strength_index(self) -> usize {
+        self.value().strength_index()
+    }
+
+    /// A canonical hand of given strength index from 1 to 7462.
+    pub fn from_strength_index(index: usize) -> Self {
+        assert!((1 ..= DISTINCT_HAND_VALUE_COUNT).contains(&index), "Strength index {} is out of range", index);
+        strength_table().hands[index - 1]
+    }
+
+    /// See HandValue::percentile().
+    pub fn percentile(self) -> f64 {
+        self.value().percentile()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::HandRank;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn best(cards: &[NonJokerCard]) -> BestFiveHand {
+        TotalHand::new(cards).find_best_five_hand().unwrap()
+    }
+
+    #[test]
+    fn royal_flush_is_the_strongest() {
+        let royal = best(&[card(Suit::Heart, 1), card(Suit::Heart, 13), card(Suit::Heart, 12), card(Suit::Heart, 11), card(Suit::Heart, 10)]);
+        assert_eq!(1, royal.strength_index());
+        assert_eq!(HandRank::RoyalFlush, BestFiveHand::from_strength_index(1).hand_rank());
+    }
+
+    #[test]
+    fn seven_high_is_the_weakest() {
+        let seven_high = best(&[card(Suit::Heart, 7), card(Suit::Club, 5), card(Suit::Heart, 4), card(Suit::Heart, 3), card(Suit::Heart, 2)]);
+        assert_eq!(DISTINCT_HAND_VALUE_COUNT, seven_high.strength_index());
+    }
+
+    #[test]
+    fn categories_start_at_known_indices() {
+        let starts = [
+            (2, HandRank::StraightFlush),
+            (11, HandRank::FourOfAKind),
+            (167, HandRank::FullHouse),
+            (323, HandRank::Flush),
+            (1600, HandRank::Straight),
+            (1610, HandRank::ThreeOfAKind),
+            (2468, HandRank::TwoPairs),
+            (3326, HandRank::Pair),
+            (6186, HandRank::HighCard),
+        ];
+        for (index, rank) in starts.iter() {
+            assert_eq!(*rank, BestFiveHand::from_strength_index(*index).hand_rank());
+            assert!(BestFiveHand::from_strength_index(index - 1).hand_rank() > *rank);
+        }
+    }
+
+    #[test]
+    fn index_is_inverse_of_from_strength_index() {
+        for index in (1 ..= DISTINCT_HAND_VALUE_COUNT).step_by(7) {
+            assert_eq!(index, HandValue::from_strength_index(index).strength_index());
+        }
+    }
+
+    #[test]
+    fn suits_do_not_change_index_of_same_value() {
+        let a = best(&[card(Suit::Heart, 9), card(Suit::Club, 9), card(Suit::Heart, 4), card(Suit::Spade, 3), card(Suit::Heart, 2)]);
+        let b = best(&[card(Suit::Diamond, 9), card(Suit::Spade, 9), card(Suit::Club, 4), card(Suit::Club, 3), card(Suit::Heart, 2)]);
+        assert_eq!(a.strength_index(), b.strength_index());
+    }
+
+    #[test]
+    fn percentile_grows_with_strength() {
+        let royal = BestFiveHand::from_strength_index(1).percentile();
+        let worst = BestFiveHand::from_strength_index(DISTINCT_HAND_VALUE_COUNT).percentile();
+        assert!((royal - (1.0 - 2.0 / FIVE_CARD_HAND_COUNT as f64)).abs() < 1e-12);
+        // 1020 hands are seven high
+        assert!((worst - 510.0 / FIVE_CARD_HAND_COUNT as f64).abs() < 1e-12);
+        // about half of all hands are high cards
+        let best_high_card = HandValue::from_strength_index(6186).percentile();
+        assert!(best_high_card > 0.49 && best_high_card < 0.51);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn index_0_is_out_of_range() {
+        BestFiveHand::from_strength_index(0);
+    }
+}
@@ -1,4 +1,5 @@
 use super::{ Board, StartingHand };
+use crate::error::HoldemError;
 use playing_card::card:: { CardRank, NonJokerCard, Suit };
 use std::collections::{ HashMap, HashSet };
 
@@ -11,10 +12,13 @@ pub struct TotalHand {
     ranks_of_straight: RanksOfStraight,
 }
 
+/// The maximum number of cards in TotalHand, which are 2 hole cards and 5 board cards.
+pub const MAX_CARDS: usize = 7;
+
 impl TotalHand {
     pub fn new(cards: &[NonJokerCard]) -> Self {
-        assert!(cards.len() <= 7, "The maximum number of cards in TotalHand is 7");
-        let mut sortable_cards: Vec<NonJokerCard> = cards.iter().map(|c| *c).collect();
+        assert!(cards.len() <= MAX_CARDS, "The maximum number of cards in TotalHand is {}", MAX_CARDS);
+        let mut sortable_cards: Vec<NonJokerCard> = cards.to_vec();
         sortable_cards.sort_by(|a, b| super::cmp_cards(*a, *b).reverse());
         let mut rank_counts = HashMap::new();
         let mut suit_counts = HashMap::new();
@@ -25,7 +29,7 @@ impl TotalHand {
             *rc += 1;
             let sc = suit_counts.entry(c.suit()).or_insert(0);
             *sc += 1;
-            bit_of_cards = bit_of_cards | 2u16.pow(u32::from(c.rank().to_int()-1));
+            bit_of_cards |= 2u16.pow(u32::from(c.rank().to_int()-1));
         }
 
         let ranks_of_straight = RanksOfStraight::calculate(bit_of_cards);
@@ -38,6 +42,15 @@ impl TotalHand {
         }
     }
 
+    /// Returns an error if more than 7 cards or the same card twice are given.
+    pub fn try_new(cards: &[NonJokerCard]) -> Result<Self, HoldemError> {
+        if cards.len() > MAX_CARDS {
+            return Err(HoldemError::TooManyCards { count: cards.len(), max: MAX_CARDS });
+        }
+        super::check_no_duplicates(cards)?;
+        Ok(Self::new(cards))
+    }
+
     pub fn new_from_starting_hand_and_board(hand: StartingHand, board: &Board) -> Self {
         let cards = [
             &hand.both_cards()[..],
@@ -89,7 +102,8 @@ impl TotalHand {
             .last()
     }
 
-    /// Returns true if at least one suit has 5 or more cards.
+    /// Returns true if at least one suit has 4 or more cards.
+    /// It does not tell whether the draw uses hole cards. Use Draws to know it.
     pub fn is_flush_draw(&self) -> bool {
         self.suit_counts.iter()
             .any(|(_k, v)| *v >= 4)
@@ -99,6 +113,14 @@ impl TotalHand {
         &self.ranks_of_straight.ranks_of_head
     }
 
+    /// Returns head ranks of straights that are completed with two or fewer additional cards.
+    /// Straights already completed are included.
+    pub fn head_ranks_of_possible_straight(&self) -> &[CardRank] {
+        &self.ranks_of_straight.ranks_of_possible_head
+    }
+
+    /// Returns ranks that complete a straight with one more card.
+    /// Open-ended draws and inside draws are not distinguished. Use Draws to know it.
     pub fn straight_draw_ranks(&self) -> &[CardRank] {
         &self.ranks_of_straight.ranks_of_draw
     }
@@ -108,11 +130,13 @@ impl TotalHand {
 struct RanksOfStraight {
     ranks_of_head: Vec<CardRank>,
     ranks_of_draw: Vec<CardRank>,
+    ranks_of_possible_head: Vec<CardRank>,
 }
 
 impl RanksOfStraight {
     fn calculate(bit_of_cards: u16) -> Self {
         let mut ranks_of_head = Vec::new();
+        let mut ranks_of_possible_head = Vec::new();
         let mut rank_set_of_draw = HashSet::new();
 
         // pair of bit representing straight and its head rank
@@ -135,6 +159,9 @@ impl RanksOfStraight {
 
         for (target_bit, head_rank) in &bits_of_straight {
             let and = *target_bit & bit_of_cards;
+            if and.count_ones() >= 3 {
+                ranks_of_possible_head.push(*head_rank);
+            }
             // when straight is completed
             if and == *target_bit { 
                 ranks_of_head.push(*head_rank);
@@ -149,9 +176,9 @@ impl RanksOfStraight {
             }
         };
 
-        let mut ranks_of_draw: Vec<CardRank> = rank_set_of_draw.iter().map(|r| *r).collect();
+        let mut ranks_of_draw: Vec<CardRank> = rank_set_of_draw.iter().copied().collect();
         ranks_of_draw.sort_by(|a, b| super::cmp_card_ranks(*a, *b).reverse());
-        Self { ranks_of_head, ranks_of_draw}
+        Self { ranks_of_head, ranks_of_draw, ranks_of_possible_head }
     }
 }
 
@@ -160,6 +187,7 @@ impl RanksOfStraight {
 mod test {
     use crate::Phase;
     use super::*;
+    use playing_card::error::CardError;
 
     #[test]
     fn can_construct_from_hand_and_board() {
@@ -191,6 +219,15 @@ mod test {
         TotalHand::new(&cards); // Be panic!
     }
 
+    #[test]
+    fn too_many_or_duplicate_cards_are_errors() {
+        let cards: Vec<NonJokerCard> = (1 ..= 8).map(|r| NonJokerCard::new(Suit::Heart, CardRank::new(r))).collect();
+        assert_eq!(Err(HoldemError::TooManyCards { count: 8, max: 7 }), TotalHand::try_new(&cards));
+        let duplicate = [cards[0], cards[1], cards[0]];
+        assert_eq!(Err(HoldemError::Card(CardError::DuplicateCard(cards[0]))), TotalHand::try_new(&duplicate));
+        assert_eq!(Ok(TotalHand::new(&cards[.. 7])), TotalHand::try_new(&cards[.. 7]));
+    }
+
     #[test]
     fn cards_should_get_sorted_in_descending_order() {
         let cards = vec![
@@ -670,6 +707,47 @@ mod test {
         assert_eq!(&expected, hand.head_ranks_of_straight());
     }
 
+    #[test]
+    fn straights_with_3_of_5_ranks_are_possible() {
+        let cards = vec![
+            NonJokerCard::new(Suit::Heart, CardRank::new(9)),
+            NonJokerCard::new(Suit::Club, CardRank::new(8)),
+            NonJokerCard::new(Suit::Spade, CardRank::new(7)),
+        ];
+        let hand = TotalHand::new(&cards);
+        let expected = [
+            CardRank::Jack,
+            CardRank::new(10),
+            CardRank::new(9),
+        ];
+        assert_eq!(&expected, hand.head_ranks_of_possible_straight());
+    }
+
+    #[test]
+    fn wheel_is_possible_with_ace_and_2_low_ranks() {
+        let cards = vec![
+            NonJokerCard::new(Suit::Heart, CardRank::Ace),
+            NonJokerCard::new(Suit::Club, CardRank::new(2)),
+            NonJokerCard::new(Suit::Spade, CardRank::new(4)),
+        ];
+        let hand = TotalHand::new(&cards);
+        let expected = [
+            CardRank::new(5),
+        ];
+        assert_eq!(&expected, hand.head_ranks_of_possible_straight());
+    }
+
+    #[test]
+    fn straight_is_not_possible_with_scattered_ranks() {
+        let cards = vec![
+            NonJokerCard::new(Suit::Heart, CardRank::King),
+            NonJokerCard::new(Suit::Club, CardRank::new(7)),
+            NonJokerCard::new(Suit::Spade, CardRank::new(2)),
+        ];
+        let hand = TotalHand::new(&cards);
+        assert!(hand.head_ranks_of_possible_straight().is_empty());
+    }
+
     #[test]
     fn same_suit_4_cards_are_flush_draw() {
         let cards = vec![
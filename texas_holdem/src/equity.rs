@@ -0,0 +1,430 @@
+use crate::Phase;
+use crate::card::{ Board, HandClass, HandValue, StartingHand, TotalHand };
+use crate::parallel::Executor;
+use crate::range::Range;
+use playing_card::card::NonJokerCard;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::io::{ self, BufRead, Write };
+
+/// Largest number of opponents in the pre-flop equity table.
+pub const MAX_OPPONENTS: usize = 9;
+
+const TABLE_VERSION: u32 = 1;
+
+/// Number of runouts a thread takes at a time in exact equities.
+const RUNOUTS_PER_PART: usize = 1024;
+
+/// Number of runouts a thread samples at a time in sampled equities.
+const TRIALS_PER_PART: usize = 1024;
+
+/// Share of the pot each hand wins at showdown on a board with 5 cards.
+/// Tied hands split the pot.
+pub fn showdown_shares(hands: &[StartingHand], board: &Board) -> Vec<f64> {
+    assert_eq!(Phase::River, board.current_phase(), "Showdown needs a board with 5 cards");
+    shares_on(hands, &board.cards())
+}
+
+fn shares_on(hands: &[StartingHand], board_cards: &[NonJokerCard]) -> Vec<f64> {
+    let values: Vec<HandValue> = hands.iter()
+        .map(|h| {
+            TotalHand::new(&[&h.both_cards()[..], board_cards].concat())
+                .find_best_five_hand()
+                .expect("best five hand not found with 7 cards")
+                .value()
+        })
+        .collect();
+    let best = *values.iter().max().expect("No hands are given");
+    let winner_count = values.iter().filter(|v| **v == best).count();
+    values.iter()
+        .map(|v| if *v == best { 1.0 / winner_count as f64 } else { 0.0 })
+        .collect()
+}
+
+fn add_shares(totals: &mut [f64], shares: &[f64]) {
+    for (t, s) in totals.iter_mut().zip(shares.iter()) {
+        *t += s;
+    }
+}
+
+/// Equities of the hands on the board, found by enumerating every runout to the river on the executor.
+/// Runouts are made of the cards left in the deck of the board except the cards of the hands.
+pub fn exact_equities(hands: &[StartingHand], board: &Board, executor: &Executor) -> Vec<f64> {
+    let board_cards = board.cards();
+    let dead_cards: Vec<NonJokerCard> = hands.iter().flat_map(|h| h.both_cards()).collect();
+    let runouts = board.runouts(&dead_cards);
+    let runout_count = runouts.len();
+    let missing = 5 - board_cards.len();
+    let totals = executor.enumerate(
+        runouts,
+        RUNOUTS_PER_PART,
+        |mut part| {
+            let mut totals = vec![0.0; hands.len()];
+            // the board cards followed by each runout
+            let mut river_board = [&board_cards[..], &board.remaining_cards()[.. missing]].concat();
+            while part.next_into(&mut river_board[board_cards.len() ..]) {
+                add_shares(&mut totals, &shares_on(hands, &river_board));
+            }
+            totals
+        },
+        |mut a, b| {
+            add_shares(&mut a, &b);
+            a
+        },
+    ).expect("No cards are left for the runouts");
+    totals.iter().map(|t| t / runout_count as f64).collect()
+}
+
+/// Estimates equities of the hands on the board with given number of runouts sampled on the executor.
+/// The same seed gives the same equities with any number of threads.
+pub fn sample_equities(hands: &[StartingHand], board: &Board, trials: usize, seed: u64, executor: &Executor) -> Vec<f64> {
+    assert!(trials > 0, "Equities need 1 or more trials");
+    let board_cards = board.cards();
+    let dead_cards: Vec<NonJokerCard> = hands.iter().flat_map(|h| h.both_cards()).collect();
+    let live_cards: Vec<NonJokerCard> = board.remaining_cards().iter().filter(|c| !dead_cards.contains(c)).copied().collect();
+    let missing = 5 - board_cards.len();
+    // every part has its own seed, so that it deals the same runouts on any thread
+    let parts = (0 .. trials.div_ceil(TRIALS_PER_PART))
+        .map(|p| (p, TRIALS_PER_PART.min(trials - p * TRIALS_PER_PART)));
+    let mut totals = vec![0.0; hands.len()];
+    executor.for_each_ordered(
+        parts,
+        |(p, count)| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(p as u64));
+            let mut totals = vec![0.0; hands.len()];
+            for _ in 0 .. count {
+                let runout: Vec<NonJokerCard> = live_cards.choose_multiple(&mut rng, missing).copied().collect();
+                add_shares(&mut totals, &shares_on(hands, &[&board_cards[..], &runout[..]].concat()));
+            }
+            totals
+        },
+        |part_totals| add_shares(&mut totals, &part_totals),
+    );
+    totals.iter().map(|t| t / trials as f64).collect()
+}
+
+/// Estimates the equity of a hand against given number of opponents with random hands.
+pub fn sample_equity_against_random(hand: StartingHand, opponent_count: usize, trials: usize) -> f64 {
+    assert!(
+        (1 ..= MAX_OPPONENTS).contains(&opponent_count),
+        "The number of opponents must be 1 to {}", MAX_OPPONENTS
+    );
+    let mut total = 0.0;
+    for _ in 0 .. trials {
+        let mut board = Board::new_without_cards(&hand.both_cards());
+        let mut hands = vec![hand];
+        hands.extend(board.deal_starting_hands(opponent_count));
+        board.deal_cards_until(Phase::River);
+        total += showdown_shares(&hands, &board)[0];
+    }
+    total / trials as f64
+}
+
+/// Estimates the equity of a hand class against another hand class heads-up.
+/// Combos of the opponent are used in turn, skipping ones sharing cards with the hand.
+pub fn sample_equity_of_classes(hand: HandClass, opponent: HandClass, trials: usize) -> f64 {
+    // every combo of a class has the same equity against a class by suit symmetry
+    let hand = hand.starting_hands()[0];
+    let opponent_hands: Vec<StartingHand> = opponent.starting_hands().into_iter()
+        .filter(|o| o.both_cards().iter().all(|c| !hand.both_cards().contains(c)))
+        .collect();
+    let mut total = 0.0;
+    for n in 0 .. trials {
+        let opponent_hand = opponent_hands[n % opponent_hands.len()];
+        let dead_cards = [hand.higher_card(), hand.lower_card(), opponent_hand.higher_card(), opponent_hand.lower_card()];
+        let mut board = Board::new_without_cards(&dead_cards);
+        board.deal_cards_until(Phase::River);
+        total += showdown_shares(&[hand, opponent_hand], &board)[0];
+    }
+    total / trials as f64
+}
+
+/// Pre-flop equities of the 169 hand classes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PreFlopEquityTable {
+    trials: usize,
+    against_random: Vec<[f64; MAX_OPPONENTS]>,
+    heads_up: Vec<f64>,
+}
+
+impl PreFlopEquityTable {
+    /// `against_random` has 169 rows of equities against 1 to 9 random opponents,
+    /// and `heads_up` is the 169x169 matrix in row-major order. Both are in order of HandClass::index().
+    pub fn new(trials: usize, against_random: Vec<[f64; MAX_OPPONENTS]>, heads_up: Vec<f64>) -> Self {
+        assert_eq!(HandClass::COUNT, against_random.len(), "Equities against random hands must have 169 rows");
+        assert_eq!(HandClass::COUNT * HandClass::COUNT, heads_up.len(), "Heads-up equities must have 169x169 values");
+        Self { trials, against_random, heads_up }
+    }
+
+    /// Number of trials used to estimate each value.
+    pub fn trials(&self) -> usize {
+        self.trials
+    }
+
+    pub fn against_random(&self, hand: StartingHand, opponent_count: usize) -> f64 {
+        self.class_against_random(hand.class(), opponent_count)
+    }
+
+    pub fn class_against_random(&self, class: HandClass, opponent_count: usize) -> f64 {
+        assert!(
+            (1 ..= MAX_OPPONENTS).contains(&opponent_count),
+            "The number of opponents must be 1 to {}", MAX_OPPONENTS
+        );
+        self.against_random[class.index()][opponent_count - 1]
+    }
+
+    pub fn heads_up(&self, hand: StartingHand, opponent: StartingHand) -> f64 {
+        self.class_heads_up(hand.class(), opponent.class())
+    }
+
+    pub fn class_heads_up(&self, class: HandClass, opponent: HandClass) -> f64 {
+        self.heads_up[class.index() * HandClass::COUNT + opponent.index()]
+    }
+
+    /// Equity of a hand against hands in a range weighted by their weights. Hands sharing a card with the hand
+    /// are not counted. None if no hands are left in the range.
+    pub fn against_range(&self, hand: StartingHand, range: &Range) -> Option<f64> {
+        let (total, weight) = range.without_cards(&hand.both_cards()).hands().iter()
+            .fold((0.0, 0.0), |(total, weight), (o, w)| (total + w * self.heads_up(hand, *o), weight + w));
+        if weight > 0.0 { Some(total / weight) } else { None }
+    }
+
+    /// Writes the table in CSV. Rows are hand classes, and columns are equities against
+    /// 1 to 9 random opponents followed by heads-up equities against each class.
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "version,{}", TABLE_VERSION)?;
+        writeln!(writer, "trials,{}", self.trials)?;
+        writeln!(writer, "{}", column_head())?;
+
+        for class in HandClass::all() {
+            let mut row = vec![class.summary()];
+            row.extend(self.against_random[class.index()].iter().map(|e| format!("{:.6}", e)));
+            row.extend(HandClass::all().iter().map(|o| format!("{:.6}", self.class_heads_up(class, *o))));
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a table written by save().
+    pub fn load<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = reader.lines();
+        let mut next_line = || lines.next().unwrap_or_else(|| Err(invalid(String::from("Unexpected end of file"))));
+
+        let version = next_line()?;
+        if version != format!("version,{}", TABLE_VERSION) {
+            return Err(invalid(format!("Unsupported version: {}", version)));
+        }
+        let trials_line = next_line()?;
+        let trials = trials_line.strip_prefix("trials,")
+            .and_then(|t| t.parse().ok())
+            .ok_or_else(|| invalid(format!("Invalid trials: {}", trials_line)))?;
+        let head = next_line()?;
+        if head != column_head() {
+            return Err(invalid(format!("Invalid column head: {}", head)));
+        }
+
+        let mut against_random = vec![[0.0; MAX_OPPONENTS]; HandClass::COUNT];
+        let mut heads_up = vec![0.0; HandClass::COUNT * HandClass::COUNT];
+        let mut found = [false; HandClass::COUNT];
+        for _ in 0 .. HandClass::COUNT {
+            let line = next_line()?;
+            let columns: Vec<&str> = line.split(',').collect();
+            if columns.len() != 1 + MAX_OPPONENTS + HandClass::COUNT {
+                return Err(invalid(format!("Invalid number of columns: {}", line)));
+            }
+            let class = HandClass::from_summary(columns[0])
+                .ok_or_else(|| invalid(format!("Invalid hand: {}", columns[0])))?;
+            let mut values = Vec::new();
+            for c in columns[1 ..].iter() {
+                values.push(c.parse::<f64>().map_err(|_| invalid(format!("Invalid equity: {}", c)))?);
+            }
+            let index = class.index();
+            found[index] = true;
+            against_random[index].copy_from_slice(&values[.. MAX_OPPONENTS]);
+            heads_up[index * HandClass::COUNT .. (index + 1) * HandClass::COUNT].copy_from_slice(&values[MAX_OPPONENTS ..]);
+        }
+        if found.contains(&false) {
+            return Err(invalid(String::from("Some hands are missing")));
+        }
+        Ok(Self::new(trials, against_random, heads_up))
+    }
+}
+
+/// Column head of the table, which is the hand followed by the numbers of random opponents and the hand classes.
+fn column_head() -> String {
+    let mut head = vec![String::from("hand")];
+    head.extend((1 ..= MAX_OPPONENTS).map(|n| format!("vs{}", n)));
+    head.extend(HandClass::all().iter().map(|c| c.summary()));
+    head.join(",")
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn sample_table() -> PreFlopEquityTable {
+        let against_random = (0 .. HandClass::COUNT)
+            .map(|i| {
+                let mut row = [0.0; MAX_OPPONENTS];
+                for (n, e) in row.iter_mut().enumerate() {
+                    *e = 1.0 / (i + n + 2) as f64;
+                }
+                row
+            })
+            .collect();
+        let heads_up = (0 .. HandClass::COUNT * HandClass::COUNT).map(|i| (i % 1000) as f64 / 1000.0).collect();
+        PreFlopEquityTable::new(100, against_random, heads_up)
+    }
+
+    #[test]
+    fn winner_takes_whole_pot() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Club, 2), card(Suit::Diamond, 7), card(Suit::Spade, 13), card(Suit::Heart, 9), card(Suit::Club, 4),
+        ]);
+        let hands = [
+            StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1)),
+            StartingHand::new(card(Suit::Heart, 13), card(Suit::Heart, 12)),
+        ];
+        assert_eq!(vec![1.0, 0.0], showdown_shares(&hands, &board));
+    }
+
+    #[test]
+    fn tied_hands_split_pot() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Club, 10), card(Suit::Diamond, 11), card(Suit::Spade, 12), card(Suit::Heart, 13), card(Suit::Club, 1),
+        ]);
+        let hands = [
+            StartingHand::new(card(Suit::Spade, 2), card(Suit::Heart, 3)),
+            StartingHand::new(card(Suit::Heart, 4), card(Suit::Heart, 5)),
+        ];
+        assert_eq!(vec![0.5, 0.5], showdown_shares(&hands, &board));
+    }
+
+    #[test]
+    #[should_panic(expected = "5 cards")]
+    fn should_panic_on_showdown_before_river() {
+        let board = Board::new_with_cards(&[card(Suit::Club, 10), card(Suit::Diamond, 11), card(Suit::Spade, 12)]);
+        showdown_shares(&[StartingHand::new(card(Suit::Spade, 2), card(Suit::Heart, 3))], &board);
+    }
+
+    fn flush_draw_against_set() -> ([StartingHand; 2], Board) {
+        let hands = [
+            StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 12)),
+            StartingHand::new(card(Suit::Spade, 13), card(Suit::Diamond, 13)),
+        ];
+        let board = Board::new_with_cards(&[card(Suit::Heart, 13), card(Suit::Heart, 9), card(Suit::Club, 4), card(Suit::Spade, 2)]);
+        (hands, board)
+    }
+
+    #[test]
+    fn exact_equities_count_every_runout() {
+        let (hands, board) = flush_draw_against_set();
+        // 7 of 44 rivers make the flush without pairing the board
+        let equities = exact_equities(&hands, &board, &Executor::new(2));
+        assert!((7.0 / 44.0 - equities[0]).abs() < 1e-9);
+        assert!((37.0 / 44.0 - equities[1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sampled_equities_depend_on_seed_but_not_on_threads() {
+        let (hands, board) = flush_draw_against_set();
+        let equities = sample_equities(&hands, &board, 3000, 5, &Executor::new(1));
+        assert_eq!(equities, sample_equities(&hands, &board, 3000, 5, &Executor::new(4)));
+        assert!((7.0 / 44.0 - equities[0]).abs() < 0.05);
+    }
+
+    #[test]
+    fn aces_are_favorite_against_random_hand() {
+        let aces = StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1));
+        assert!(sample_equity_against_random(aces, 1, 200) > 0.6);
+    }
+
+    #[test]
+    fn same_classes_are_even_heads_up() {
+        let class = HandClass::from_summary("AKo").unwrap();
+        let equity = sample_equity_of_classes(class, class, 200);
+        assert!(equity > 0.4 && equity < 0.6);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of opponents")]
+    fn should_panic_with_too_many_opponents() {
+        let aces = StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1));
+        sample_equity_against_random(aces, 10, 1);
+    }
+
+    #[test]
+    fn table_is_queried_by_starting_hand() {
+        let table = sample_table();
+        let hand = StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 13));
+        let opponent = StartingHand::new(card(Suit::Club, 2), card(Suit::Heart, 2));
+        assert_eq!(table.class_against_random(hand.class(), 3), table.against_random(hand, 3));
+        assert_eq!(table.class_heads_up(hand.class(), opponent.class()), table.heads_up(hand, opponent));
+    }
+
+    #[test]
+    fn equity_against_range_is_weighted_without_blocked_hands() {
+        let table = sample_table();
+        let hand = StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1));
+        let range = Range::parse("AA,KK:0.5").unwrap();
+        let aces = table.class_heads_up(hand.class(), HandClass::from_summary("AA").unwrap());
+        let kings = table.class_heads_up(hand.class(), HandClass::from_summary("KK").unwrap());
+        // only 1 combo of aces is left
+        let expected = (aces + 0.5 * 6.0 * kings) / 4.0;
+        assert!((expected - table.against_range(hand, &range).unwrap()).abs() < 1e-12);
+        assert_eq!(None, table.against_range(hand, &Range::parse("AA:0.0").unwrap()));
+    }
+
+    #[test]
+    fn saved_table_can_be_loaded() {
+        let table = sample_table();
+        let mut buffer = Vec::new();
+        table.save(&mut buffer).unwrap();
+        let loaded = PreFlopEquityTable::load(&buffer[..]).unwrap();
+        assert_eq!(100, loaded.trials());
+        for class in HandClass::all() {
+            for n in 1 ..= MAX_OPPONENTS {
+                let diff = table.class_against_random(class, n) - loaded.class_against_random(class, n);
+                assert!(diff.abs() < 1e-6);
+            }
+            for opponent in HandClass::all() {
+                assert_eq!(table.class_heads_up(class, opponent), loaded.class_heads_up(class, opponent));
+            }
+        }
+    }
+
+    #[test]
+    fn table_of_other_version_is_not_loaded() {
+        let mut buffer = Vec::new();
+        sample_table().save(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap().replacen("version,1", "version,0", 1);
+        let error = PreFlopEquityTable::load(text.as_bytes()).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn table_with_reordered_columns_is_not_loaded() {
+        let mut buffer = Vec::new();
+        sample_table().save(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap().replacen("hand,vs1,vs2", "hand,vs2,vs1", 1);
+        let error = PreFlopEquityTable::load(text.as_bytes()).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        assert!(error.to_string().contains("column head"));
+    }
+
+    #[test]
+    fn truncated_table_is_not_loaded() {
+        let mut buffer = Vec::new();
+        sample_table().save(&mut buffer).unwrap();
+        let error = PreFlopEquityTable::load(&buffer[.. buffer.len() / 2]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+}
@@ -0,0 +1,39 @@
+use playing_card::error::CardError;
+use std::error;
+use std::fmt;
+
+/// Errors of invalid hands and boards.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum HoldemError {
+    /// Invalid cards, or a deck without enough cards to deal.
+    Card(CardError),
+    /// More cards are given than allowed.
+    TooManyCards { count: usize, max: usize },
+    /// A number of board cards which is not 0, 3, 4 or 5.
+    InvalidBoardSize(usize),
+}
+
+impl fmt::Display for HoldemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Card(e) => write!(f, "{}", e),
+            Self::TooManyCards { count, max } => write!(f, "{} cards are given but the maximum number of cards is {}", count, max),
+            Self::InvalidBoardSize(count) => write!(f, "The number of cards on board must be 0, 3, 4 or 5 but {}", count),
+        }
+    }
+}
+
+impl error::Error for HoldemError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Card(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<CardError> for HoldemError {
+    fn from(e: CardError) -> Self {
+        Self::Card(e)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::card::{ Board, HandValue, StartingHand, TotalHand };
+use playing_card::card::NonJokerCard;
+use playing_card::combination::Combinations;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::cmp::Ordering;
+
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+fn result_index(ordering: Ordering) -> usize {
+    match ordering {
+        Ordering::Greater => AHEAD,
+        Ordering::Equal => TIED,
+        Ordering::Less => BEHIND,
+    }
+}
+
+fn value_of(hand: StartingHand, board_cards: &[NonJokerCard]) -> HandValue {
+    TotalHand::new(&[&hand.both_cards()[..], board_cards].concat())
+        .find_best_five_hand()
+        .expect("best five hand not found with 5 or more cards")
+        .value()
+}
+
+/// Hand strength and hand potential of a starting hand on a board against a random opponent hand,
+/// found by enumerating every opponent hand and every runout to the river, or some sampled runouts.
+///
+/// Enumeration on flop evaluates about a million showdowns.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandStrength {
+    strength: f64,
+    positive_potential: f64,
+    negative_potential: f64,
+    /// Hand strength on the river of each runout.
+    river_strengths: Vec<f64>,
+}
+
+impl HandStrength {
+    /// Board must have 3 or more cards.
+    pub fn calculate(hand: StartingHand, board: &Board) -> Self {
+        let (board_cards, live_cards) = cards_around(hand, board);
+        let mut runouts = Combinations::from_slice(&live_cards, 5 - board_cards.len());
+        Self::on_runouts(hand, &board_cards, &live_cards, |runout| runouts.next_into(runout))
+    }
+
+    /// Hand strength with given number of runouts sampled with the generator instead of every runout.
+    /// There is a single runout on the river. Board must have 3 or more cards.
+    pub fn sample<R: Rng>(hand: StartingHand, board: &Board, runout_count: usize, rng: &mut R) -> Self {
+        let (board_cards, live_cards) = cards_around(hand, board);
+        let mut remaining = if board_cards.len() == 5 { 1 } else { runout_count };
+        Self::on_runouts(hand, &board_cards, &live_cards, |runout| {
+            if remaining == 0 {
+                return false;
+            }
+            remaining -= 1;
+            let missing = runout.len();
+            for (r, c) in runout.iter_mut().zip(live_cards.choose_multiple(rng, missing)) {
+                *r = *c;
+            }
+            true
+        })
+    }
+
+    /// `next_runout` writes the next runout of live cards and returns true, or returns false if no runout is left.
+    fn on_runouts<F>(hand: StartingHand, board_cards: &[NonJokerCard], live_cards: &[NonJokerCard], mut next_runout: F) -> Self
+    where
+        F: FnMut(&mut [NonJokerCard]) -> bool,
+    {
+        let dead_cards = [board_cards, &hand.both_cards()[..]].concat();
+        let opponents: Vec<StartingHand> = StartingHand::combos_without(&dead_cards).collect();
+
+        let value = value_of(hand, board_cards);
+        let current_results: Vec<usize> = opponents.iter()
+            .map(|o| result_index(value.cmp(&value_of(*o, board_cards))))
+            .collect();
+        let mut current_counts = [0.0; 3];
+        for r in current_results.iter() {
+            current_counts[*r] += 1.0;
+        }
+        let strength = share_of_wins(current_counts);
+
+        // counts of opponent hands by results now and on the river
+        let mut counts = [[0.0; 3]; 3];
+        let mut river_strengths = Vec::new();
+        // the board cards followed by each runout
+        let mut river_board = [board_cards, &live_cards[.. 5 - board_cards.len()]].concat();
+        while next_runout(&mut river_board[board_cards.len() ..]) {
+            let runout = &river_board[board_cards.len() ..];
+            let river_value = value_of(hand, &river_board);
+            let mut river_counts = [0.0; 3];
+            for (o, current) in opponents.iter().zip(current_results.iter()) {
+                if o.both_cards().iter().any(|c| runout.contains(c)) {
+                    continue;
+                }
+                let river = result_index(river_value.cmp(&value_of(*o, &river_board)));
+                counts[*current][river] += 1.0;
+                river_counts[river] += 1.0;
+            }
+            river_strengths.push(share_of_wins(river_counts));
+        }
+
+        let totals: Vec<f64> = counts.iter().map(|row| row.iter().sum()).collect();
+        let ratio = |numerator: f64, denominator: f64| if denominator > 0.0 { numerator / denominator } else { 0.0 };
+        let positive_potential = ratio(
+            counts[BEHIND][AHEAD] + counts[BEHIND][TIED] / 2.0 + counts[TIED][AHEAD] / 2.0,
+            totals[BEHIND] + totals[TIED] / 2.0,
+        );
+        let negative_potential = ratio(
+            counts[AHEAD][BEHIND] + counts[TIED][BEHIND] / 2.0 + counts[AHEAD][TIED] / 2.0,
+            totals[AHEAD] + totals[TIED] / 2.0,
+        );
+        Self { strength, positive_potential, negative_potential, river_strengths }
+    }
+
+    /// Share of opponent hands the hand beats now, where ties count as half.
+    pub fn strength(&self) -> f64 {
+        self.strength
+    }
+
+    /// Chance of beating all of given number of opponents now, assuming their hands are independent.
+    pub fn strength_against(&self, opponent_count: usize) -> f64 {
+        self.strength.powi(opponent_count as i32)
+    }
+
+    /// Chance that the hand behind now gets ahead by the river, where ties count as half.
+    pub fn positive_potential(&self) -> f64 {
+        self.positive_potential
+    }
+
+    /// Chance that the hand ahead now falls behind by the river, where ties count as half.
+    pub fn negative_potential(&self) -> f64 {
+        self.negative_potential
+    }
+
+    /// Effective hand strength against given number of opponents, HS * (1 - NPot) + (1 - HS) * PPot.
+    pub fn effective_strength(&self, opponent_count: usize) -> f64 {
+        let strength = self.strength_against(opponent_count);
+        strength * (1.0 - self.negative_potential) + (1.0 - strength) * self.positive_potential
+    }
+
+    /// Hand strength on the river of each runout. It has a single value on the river.
+    pub fn river_strengths(&self) -> &[f64] {
+        &self.river_strengths
+    }
+
+    /// Mean of river strengths, which is the equity against a random hand.
+    pub fn expected_strength(&self) -> f64 {
+        self.river_strengths.iter().sum::<f64>() / self.river_strengths.len() as f64
+    }
+
+    /// Mean of squared river strengths (EHS²), which rewards hands with potential.
+    pub fn expected_squared_strength(&self) -> f64 {
+        self.river_strengths.iter().map(|s| s * s).sum::<f64>() / self.river_strengths.len() as f64
+    }
+
+    /// Shares of runouts by river strength in given number of equal bins from 0 to 1.
+    pub fn histogram(&self, bin_count: usize) -> Vec<f64> {
+        assert!(bin_count > 0, "Histogram needs 1 or more bins");
+        let mut bins = vec![0.0; bin_count];
+        let share = 1.0 / self.river_strengths.len() as f64;
+        for s in self.river_strengths.iter() {
+            let i = ((s * bin_count as f64) as usize).min(bin_count - 1);
+            bins[i] += share;
+        }
+        bins
+    }
+}
+
+/// Cards on the board and cards which are neither on the board nor in the hand.
+fn cards_around(hand: StartingHand, board: &Board) -> (Vec<NonJokerCard>, Vec<NonJokerCard>) {
+    let board_cards = board.cards();
+    assert!(board_cards.len() >= 3, "Hand strength cannot be calculated before flop");
+    let live_cards = NonJokerCard::all().into_iter()
+        .filter(|c| !board_cards.contains(c) && !hand.both_cards().contains(c))
+        .collect();
+    (board_cards, live_cards)
+}
+
+fn share_of_wins(counts: [f64; 3]) -> f64 {
+    (counts[AHEAD] + counts[TIED] / 2.0) / counts.iter().sum::<f64>()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, Suit };
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn turn() -> Board {
+        Board::new_with_cards(&[card(Suit::Heart, 13), card(Suit::Heart, 9), card(Suit::Club, 4), card(Suit::Spade, 2)])
+    }
+
+    #[test]
+    fn nuts_on_river_has_full_strength_and_no_potential() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Heart, 13), card(Suit::Heart, 9), card(Suit::Club, 4), card(Suit::Spade, 2), card(Suit::Heart, 3),
+        ]);
+        let strength = HandStrength::calculate(StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 12)), &board);
+        assert_eq!(1.0, strength.strength());
+        assert_eq!(0.0, strength.positive_potential());
+        assert_eq!(0.0, strength.negative_potential());
+        assert_eq!(&[1.0], strength.river_strengths());
+    }
+
+    #[test]
+    fn flush_draw_has_positive_potential() {
+        let draw = HandStrength::calculate(StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 5)), &turn());
+        assert!(draw.strength() < 0.7);
+        // 9 outs of 46 cards make the nut flush
+        assert!(draw.positive_potential() > 9.0 / 46.0);
+        assert!(draw.effective_strength(1) > draw.strength());
+        assert_eq!(46, draw.river_strengths().len());
+
+        let histogram = draw.histogram(5);
+        assert!((1.0 - histogram.iter().sum::<f64>()).abs() < 1e-9);
+        // a draw either hits or misses
+        assert!(histogram[4] > 0.15 && histogram[0] + histogram[1] + histogram[2] > 0.5);
+        assert!(draw.expected_squared_strength() > draw.expected_strength() * draw.expected_strength());
+    }
+
+    #[test]
+    fn sampled_runouts_are_a_part_of_all_runouts() {
+        let hand = StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 5));
+        let all = HandStrength::calculate(hand, &turn());
+        let sampled = HandStrength::sample(hand, &turn(), 10, &mut StdRng::seed_from_u64(0));
+        assert_eq!(all.strength(), sampled.strength());
+        assert_eq!(10, sampled.river_strengths().len());
+        assert!(sampled.river_strengths().iter().all(|s| all.river_strengths().contains(s)));
+        assert_eq!(sampled, HandStrength::sample(hand, &turn(), 10, &mut StdRng::seed_from_u64(0)));
+    }
+
+    #[test]
+    fn made_hand_has_negative_potential() {
+        let top_pair = HandStrength::calculate(StartingHand::new(card(Suit::Spade, 13), card(Suit::Club, 12)), &turn());
+        assert!(top_pair.strength() > 0.8);
+        assert!(top_pair.negative_potential() > 0.0);
+        assert!(top_pair.strength_against(3) < top_pair.strength());
+        assert!(top_pair.effective_strength(1) < top_pair.strength());
+    }
+
+    #[test]
+    #[should_panic(expected = "before flop")]
+    fn strength_needs_flop() {
+        HandStrength::calculate(StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 5)), &Board::new());
+    }
+}
@@ -1,8 +1,19 @@
 extern crate playing_card;
+extern crate rand;
 
 pub mod card;
+pub mod equity;
+pub mod error;
+pub mod hand_strength;
+pub mod parallel;
+pub mod position;
+pub mod range;
+
+#[cfg(feature = "serde")]
+mod serialization;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
 pub enum Phase {
     PreFlop,
     Flop,
@@ -0,0 +1,238 @@
+use playing_card::combination::Combinations;
+use std::collections::BTreeMap;
+use std::iter::Enumerate;
+use std::sync::{ Condvar, Mutex };
+use std::sync::mpsc;
+use std::thread;
+
+/// Number of parts a thread can run ahead of the results passed in order,
+/// which bounds the results waiting for the ones of earlier parts.
+const PARTS_AHEAD_PER_THREAD: usize = 4;
+
+/// Runs tasks on scoped threads and hands their results back in order of the tasks,
+/// so that merged results do not depend on the number of threads or their timing.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Executor {
+    thread_count: usize,
+}
+
+impl Executor {
+    pub fn new(thread_count: usize) -> Self {
+        assert!(thread_count > 0, "Executor needs 1 or more threads");
+        Self { thread_count }
+    }
+
+    /// Executor with as many threads as the machine can run at once.
+    pub fn with_available_threads() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+
+    pub fn thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    /// Runs the task on each part and passes the results to `on_result` on the calling thread in order of the parts.
+    /// Idle threads take the next part one by one, and a result is passed as soon as all results before it are passed.
+    /// Threads wait instead of taking a part too far ahead of the passed results, so that a slow part does not make
+    /// the results after it pile up.
+    pub fn for_each_ordered<I, R, F, G>(&self, parts: I, task: F, mut on_result: G)
+    where
+        I: IntoIterator,
+        I::IntoIter: Send,
+        I::Item: Send,
+        R: Send,
+        F: Fn(I::Item) -> R + Sync,
+        G: FnMut(R),
+    {
+        let queue = PartQueue::new(parts.into_iter(), self.thread_count * PARTS_AHEAD_PER_THREAD);
+        let (tx, rx) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0 .. self.thread_count {
+                let tx = tx.clone();
+                let (queue, task) = (&queue, &task);
+                scope.spawn(move || {
+                    let _close = CloseOnDrop(queue);
+                    while let Some((i, part)) = queue.take() {
+                        if tx.send((i, task(part))).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            drop(tx);
+
+            // lets the threads waiting for a part return if on_result panics
+            let _close = CloseOnDrop(&queue);
+            // results which came before the ones of earlier parts
+            let mut waiting = BTreeMap::new();
+            let mut next_index = 0;
+            for (i, result) in rx.iter() {
+                waiting.insert(i, result);
+                while let Some(result) = waiting.remove(&next_index) {
+                    on_result(result);
+                    next_index += 1;
+                }
+                queue.set_passed(next_index);
+            }
+        });
+    }
+
+    /// Results of the task on each part in order of the parts.
+    pub fn map<I, R, F>(&self, parts: I, task: F) -> Vec<R>
+    where
+        I: IntoIterator,
+        I::IntoIter: Send,
+        I::Item: Send,
+        R: Send,
+        F: Fn(I::Item) -> R + Sync,
+    {
+        let mut results = Vec::new();
+        self.for_each_ordered(parts, task, |r| results.push(r));
+        results
+    }
+
+    /// Splits the combinations into parts of given size at most, runs the task on each part
+    /// and merges the results from the first part. Results are the same with any number of threads.
+    pub fn enumerate<'a, T, R, F, M>(&self, combinations: Combinations<'a, T>, part_size: usize, task: F, mut merge: M) -> Option<R>
+    where
+        T: Clone + Send + Sync,
+        R: Send,
+        F: Fn(Combinations<'a, T>) -> R + Sync,
+        M: FnMut(R, R) -> R,
+    {
+        assert!(part_size > 0, "Parts must have 1 or more combinations");
+        let part_count = combinations.len().div_ceil(part_size);
+        if part_count == 0 {
+            return None;
+        }
+        let mut merged = None;
+        self.for_each_ordered(combinations.split(part_count), task, |r| {
+            merged = Some(match merged.take() {
+                Some(m) => merge(m, r),
+                None => r,
+            });
+        });
+        merged
+    }
+}
+
+/// Parts handed to threads, which are at most `window` parts ahead of the results passed in order.
+struct PartQueue<I> {
+    state: Mutex<QueueState<I>>,
+    changed: Condvar,
+    window: usize,
+}
+
+struct QueueState<I> {
+    parts: Enumerate<I>,
+    taken: usize,
+    passed: usize,
+    closed: bool,
+}
+
+impl<I: Iterator> PartQueue<I> {
+    fn new(parts: I, window: usize) -> Self {
+        let state = QueueState { parts: parts.enumerate(), taken: 0, passed: 0, closed: false };
+        Self { state: Mutex::new(state), changed: Condvar::new(), window }
+    }
+
+    /// Waits until the next part is in the window and takes it. None if no part is left or the queue is closed.
+    fn take(&self) -> Option<(usize, I::Item)> {
+        let mut state = self.state.lock().expect("another thread panicked while taking a part");
+        while !state.closed && state.taken >= state.passed + self.window {
+            state = self.changed.wait(state).expect("another thread panicked while taking a part");
+        }
+        if state.closed {
+            return None;
+        }
+        state.taken += 1;
+        state.parts.next()
+    }
+
+    fn set_passed(&self, passed: usize) {
+        self.state.lock().expect("another thread panicked while taking a part").passed = passed;
+        self.changed.notify_all();
+    }
+
+    fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.changed.notify_all();
+    }
+}
+
+/// Closes the queue when a thread stops using it, even by a panic,
+/// so that no thread keeps waiting for results which never come.
+struct CloseOnDrop<'a, I: Iterator>(&'a PartQueue<I>);
+
+impl<I: Iterator> Drop for CloseOnDrop<'_, I> {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+    use std::time::Duration;
+
+    #[test]
+    fn results_are_in_order_of_parts() {
+        // later parts finish first
+        let results = Executor::new(4).map(0 .. 8u64, |i| {
+            thread::sleep(Duration::from_millis(8 - i));
+            i * 10
+        });
+        assert_eq!(vec![0, 10, 20, 30, 40, 50, 60, 70], results);
+    }
+
+    #[test]
+    fn parts_run_ahead_of_a_slow_part_are_limited() {
+        let started = AtomicUsize::new(0);
+        let mut started_before_first_result = None;
+        Executor::new(2).for_each_ordered(
+            0 .. 100u64,
+            |i| {
+                started.fetch_add(1, Ordering::SeqCst);
+                if i == 0 {
+                    thread::sleep(Duration::from_millis(50));
+                }
+            },
+            |_| {
+                started_before_first_result.get_or_insert(started.load(Ordering::SeqCst));
+            },
+        );
+        assert!(started_before_first_result.unwrap() <= 2 * PARTS_AHEAD_PER_THREAD);
+        assert_eq!(100, started.into_inner());
+    }
+
+    #[test]
+    fn enumeration_is_the_same_with_any_number_of_threads() {
+        let sum = |threads: usize| {
+            Executor::new(threads).enumerate(
+                Combinations::new((1 .. 20).map(|n| 1.0 / n as f64).collect(), 3),
+                10,
+                |part| part.map(|c| c.iter().product::<f64>()).sum::<f64>(),
+                |a, b| a + b,
+            )
+        };
+        let single = sum(1).unwrap();
+        assert_eq!(single, sum(3).unwrap());
+        assert_eq!(single, sum(8).unwrap());
+    }
+
+    #[test]
+    fn nothing_is_enumerated_without_combinations() {
+        let result = Executor::new(2).enumerate(Combinations::new(vec![1, 2], 3), 10, |part| part.count(), |a, b| a + b);
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 or more threads")]
+    fn executor_needs_a_thread() {
+        Executor::new(0);
+    }
+}
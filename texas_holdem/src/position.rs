@@ -0,0 +1,115 @@
+/// Seat position relative to the button, in order of acting before flop.
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Position {
+    UnderTheGun,
+    UnderTheGun1,
+    UnderTheGun2,
+    MiddlePosition,
+    LoJack,
+    HiJack,
+    CutOff,
+    Button,
+    SmallBlind,
+    BigBlind,
+}
+
+impl Position {
+    pub const COUNT: usize = 10;
+
+    pub fn all() -> [Self; Self::COUNT] {
+        [
+            Self::UnderTheGun,
+            Self::UnderTheGun1,
+            Self::UnderTheGun2,
+            Self::MiddlePosition,
+            Self::LoJack,
+            Self::HiJack,
+            Self::CutOff,
+            Self::Button,
+            Self::SmallBlind,
+            Self::BigBlind,
+        ]
+    }
+
+    /// Position in all().
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Positions of a table with given number of players, in order of acting before flop.
+    /// The first to act is UTG with 4 or more players, and early positions next to UTG are left out
+    /// on a short-handed table, e.g. UTG, HJ, CO, BTN, SB and BB with 6 players.
+    /// The button posts the small blind in heads-up, and is shown as SB.
+    pub fn of_table(num_of_players: usize) -> Vec<Self> {
+        assert!((2 ..= Self::COUNT).contains(&num_of_players), "Invalid number of players");
+        let all = Self::all();
+        if num_of_players <= 3 {
+            return all[Self::COUNT - num_of_players ..].to_vec();
+        }
+        let mut positions = vec![Self::UnderTheGun];
+        positions.extend_from_slice(&all[Self::COUNT - num_of_players + 1 ..]);
+        positions
+    }
+
+    pub fn is_blind(self) -> bool {
+        matches!(self, Self::SmallBlind | Self::BigBlind)
+    }
+
+    /// Short name such as "UTG+1" or "BTN".
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::UnderTheGun => "UTG",
+            Self::UnderTheGun1 => "UTG+1",
+            Self::UnderTheGun2 => "UTG+2",
+            Self::MiddlePosition => "MP",
+            Self::LoJack => "LJ",
+            Self::HiJack => "HJ",
+            Self::CutOff => "CO",
+            Self::Button => "BTN",
+            Self::SmallBlind => "SB",
+            Self::BigBlind => "BB",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().find(|p| p.name() == name).copied()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn short_handed_table_keeps_late_positions() {
+        assert_eq!(vec![Position::SmallBlind, Position::BigBlind], Position::of_table(2));
+        assert_eq!(vec![Position::Button, Position::SmallBlind, Position::BigBlind], Position::of_table(3));
+        let names: Vec<&str> = Position::of_table(6).iter().map(|p| p.name()).collect();
+        assert_eq!(vec!["UTG", "HJ", "CO", "BTN", "SB", "BB"], names);
+    }
+
+    #[test]
+    fn full_table_has_all_positions() {
+        assert_eq!(Position::all().to_vec(), Position::of_table(10));
+    }
+
+    #[test]
+    fn index_is_position_in_all() {
+        for (i, p) in Position::all().iter().enumerate() {
+            assert_eq!(i, p.index());
+        }
+    }
+
+    #[test]
+    fn position_is_found_by_name() {
+        assert_eq!(Some(Position::UnderTheGun1), Position::from_name("UTG+1"));
+        assert_eq!(None, Position::from_name("XX"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid number of players")]
+    fn table_of_one_player_is_invalid() {
+        Position::of_table(1);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::card::{ HandClass, StartingHand };
+use playing_card::card::{ CardRank, NonJokerCard, Suit };
+
+/// Number of combinations of two cards.
+pub const COMBO_COUNT: usize = 1326;
+
+/// Position of a card in NonJokerCard::all().
+fn card_index(card: NonJokerCard) -> usize {
+    let suit = Suit::all().iter().position(|s| *s == card.suit()).unwrap();
+    suit * 13 + card.rank().to_int() as usize - 1
+}
+
+fn card_of_index(index: usize) -> NonJokerCard {
+    NonJokerCard::new(Suit::all()[index / 13], CardRank::new((index % 13 + 1) as u8))
+}
+
+impl StartingHand {
+    /// Dense index from 0 to 1325.
+    pub fn index(self) -> usize {
+        let a = card_index(self.higher_card());
+        let b = card_index(self.lower_card());
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        high * (high - 1) / 2 + low
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        assert!(index < COMBO_COUNT, "Index {} is out of range", index);
+        // the largest high with high * (high - 1) / 2 <= index
+        let mut high = 1;
+        while (high + 1) * high / 2 <= index {
+            high += 1;
+        }
+        let low = index - high * (high - 1) / 2;
+        Self::new(card_of_index(high), card_of_index(low))
+    }
+}
+
+/// Starting hands a player may hold, each with a weight from 0 to 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Range {
+    weights: Vec<f64>,
+}
+
+impl Range {
+    pub fn empty() -> Self {
+        Self { weights: vec![0.0; COMBO_COUNT] }
+    }
+
+    /// All starting hands with weight 1.
+    pub fn full() -> Self {
+        Self { weights: vec![1.0; COMBO_COUNT] }
+    }
+
+    /// Parses hand classes separated by commas, such as "QQ+,AKs,ATs+,KQo:0.5".
+    /// "+" adds classes up to the pair of aces for pairs, and up to the kicker just below the higher card otherwise.
+    /// A weight can follow a colon, and is 1 if omitted.
+    pub fn parse(notation: &str) -> Option<Self> {
+        let mut range = Self::empty();
+        for item in notation.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            let (classes, weight) = match item.split_once(':') {
+                Some((classes, weight)) => (classes, weight.parse::<f64>().ok()?),
+                None => (item, 1.0),
+            };
+            if !(0.0 ..= 1.0).contains(&weight) {
+                return None;
+            }
+            let classes = match classes.strip_suffix('+') {
+                Some(class) => classes_and_better(HandClass::from_summary(class)?),
+                None => vec![HandClass::from_summary(classes)?],
+            };
+            for class in classes {
+                range.set_class_weight(class, weight);
+            }
+        }
+        Some(range)
+    }
+
+    pub fn weight(&self, hand: StartingHand) -> f64 {
+        self.weights[hand.index()]
+    }
+
+    pub fn set_weight(&mut self, hand: StartingHand, weight: f64) {
+        assert!((0.0 ..= 1.0).contains(&weight), "Weight must be from 0 to 1");
+        self.weights[hand.index()] = weight;
+    }
+
+    pub fn set_class_weight(&mut self, class: HandClass, weight: f64) {
+        for hand in class.starting_hands() {
+            self.set_weight(hand, weight);
+        }
+    }
+
+    /// Hands with positive weights in order of StartingHand::index().
+    pub fn hands(&self) -> Vec<(StartingHand, f64)> {
+        self.weights.iter().enumerate()
+            .filter(|(_, w)| **w > 0.0)
+            .map(|(i, w)| (StartingHand::from_index(i), *w))
+            .collect()
+    }
+
+    /// Sum of weights.
+    pub fn combo_count(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.iter().all(|w| *w == 0.0)
+    }
+
+    /// The range without hands containing any of given cards, e.g. cards on the board.
+    pub fn without_cards(&self, dead_cards: &[NonJokerCard]) -> Self {
+        let mut range = self.clone();
+        for (i, w) in range.weights.iter_mut().enumerate() {
+            if StartingHand::from_index(i).both_cards().iter().any(|c| dead_cards.contains(c)) {
+                *w = 0.0;
+            }
+        }
+        range
+    }
+}
+
+/// The class and better classes of the same kind on the hand grid, e.g. TT to AA, or ATs to AKs.
+fn classes_and_better(class: HandClass) -> Vec<HandClass> {
+    let (row, column) = class.grid_position();
+    if class.is_pair() {
+        (0 ..= row).map(|i| HandClass::from_grid_position(i, i)).collect()
+    } else if class.is_suited() {
+        (row + 1 ..= column).map(|c| HandClass::from_grid_position(row, c)).collect()
+    } else {
+        (column + 1 ..= row).map(|r| HandClass::from_grid_position(r, column)).collect()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn class(summary: &str) -> HandClass {
+        HandClass::from_summary(summary).unwrap()
+    }
+
+    #[test]
+    fn index_is_inverse_of_from_index() {
+        let indices: HashSet<usize> = StartingHand::all().into_iter().map(|h| h.index()).collect();
+        assert_eq!(COMBO_COUNT, indices.len());
+        for hand in StartingHand::all() {
+            assert_eq!(hand, StartingHand::from_index(hand.index()));
+        }
+    }
+
+    #[test]
+    fn full_range_has_all_combos() {
+        assert_eq!(COMBO_COUNT as f64, Range::full().combo_count());
+        assert!(Range::empty().is_empty());
+    }
+
+    #[test]
+    fn classes_are_parsed_with_weights() {
+        let range = Range::parse("AA, KQo:0.5").unwrap();
+        assert_eq!(6.0 + 6.0, range.combo_count());
+        assert_eq!(1.0, range.weight(StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1))));
+        assert_eq!(0.5, range.weight(StartingHand::new(card(Suit::Spade, 13), card(Suit::Heart, 12))));
+        assert_eq!(0.0, range.weight(StartingHand::new(card(Suit::Spade, 13), card(Suit::Spade, 12))));
+    }
+
+    #[test]
+    fn plus_adds_better_classes() {
+        let pairs: HashSet<HandClass> = Range::parse("QQ+").unwrap().hands().iter().map(|(h, _)| h.class()).collect();
+        assert_eq!(vec![class("AA"), class("KK"), class("QQ")].into_iter().collect::<HashSet<_>>(), pairs);
+        let suited: HashSet<HandClass> = Range::parse("AJs+").unwrap().hands().iter().map(|(h, _)| h.class()).collect();
+        assert_eq!(vec![class("AKs"), class("AQs"), class("AJs")].into_iter().collect::<HashSet<_>>(), suited);
+        let offsuit: HashSet<HandClass> = Range::parse("KTo+").unwrap().hands().iter().map(|(h, _)| h.class()).collect();
+        assert_eq!(vec![class("KQo"), class("KJo"), class("KTo")].into_iter().collect::<HashSet<_>>(), offsuit);
+    }
+
+    #[test]
+    fn invalid_notations_are_not_parsed() {
+        assert_eq!(None, Range::parse("AX"));
+        assert_eq!(None, Range::parse("AA:2"));
+        assert_eq!(None, Range::parse("AA:x"));
+    }
+
+    #[test]
+    fn hands_with_dead_cards_are_removed() {
+        let range = Range::parse("AA").unwrap().without_cards(&[card(Suit::Spade, 1)]);
+        assert_eq!(3.0, range.combo_count());
+    }
+
+    #[test]
+    #[should_panic(expected = "Weight must be from 0 to 1")]
+    fn weight_over_1_is_invalid() {
+        Range::empty().set_weight(StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1)), 1.5);
+    }
+}
@@ -0,0 +1,146 @@
+//! Serde support enabled by the "serde" feature.
+//! Cards are encoded in short notations such as "Ah" as in playing_card.
+
+use crate::card::{ Board, BestFiveHand, HandClass, StartingHand, TotalHand };
+use playing_card::card::NonJokerCard;
+use serde::de::{ Deserialize, Deserializer, Error };
+use serde::ser::{ Serialize, Serializer };
+
+/// Encoded as the two cards, e.g. ["Ah","Kd"].
+impl Serialize for StartingHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.both_cards().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StartingHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cards = <[NonJokerCard; 2]>::deserialize(deserializer)?;
+        StartingHand::try_new(cards[0], cards[1]).map_err(D::Error::custom)
+    }
+}
+
+/// Encoded as HandClass::summary(), e.g. "AKs".
+impl Serialize for HandClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.summary())
+    }
+}
+
+impl<'de> Deserialize<'de> for HandClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        HandClass::from_summary(&s).ok_or_else(|| D::Error::custom(format!("Invalid hand class: {}", s)))
+    }
+}
+
+/// Encoded as the cards in descending order.
+impl Serialize for TotalHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.cards().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TotalHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cards = Vec::<NonJokerCard>::deserialize(deserializer)?;
+        TotalHand::try_new(&cards).map_err(D::Error::custom)
+    }
+}
+
+/// Encoded as the five cards, whose hand rank is evaluated again when decoded.
+impl Serialize for BestFiveHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.cards().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BestFiveHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cards = <[NonJokerCard; 5]>::deserialize(deserializer)?;
+        TotalHand::try_new(&cards)
+            .map_err(D::Error::custom)?
+            .find_best_five_hand()
+            .ok_or_else(|| D::Error::custom("best five hand not found with 5 cards"))
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardData {
+    cards: Vec<NonJokerCard>,
+    deck: Vec<NonJokerCard>,
+}
+
+/// Encoded as the cards on the board and the cards left in the deck in order of dealing,
+/// so that a hand in progress can be restored, e.g. {"cards":["Ah","Kd","2c"],"deck":["7s",...]}.
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardData { cards: self.cards(), deck: self.remaining_cards().to_vec() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = BoardData::deserialize(deserializer)?;
+        Board::try_new_with_deck(&data.cards, data.deck).map_err(D::Error::custom)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Phase;
+    use crate::card::{ HandRank, HandValue };
+    use playing_card::card::{ CardRank, Suit };
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    #[test]
+    fn hands_are_encoded_in_short_notation() {
+        let hand = StartingHand::new(card(Suit::Diamond, 13), card(Suit::Heart, 1));
+        assert_eq!("[\"Ah\",\"Kd\"]", serde_json::to_string(&hand).unwrap());
+        assert_eq!(hand, serde_json::from_str("[\"Kd\",\"Ah\"]").unwrap());
+        assert_eq!("\"AKo\"", serde_json::to_string(&hand.class()).unwrap());
+        assert_eq!(hand.class(), serde_json::from_str("\"AKo\"").unwrap());
+    }
+
+    #[test]
+    fn invalid_hands_are_not_decoded() {
+        assert!(serde_json::from_str::<StartingHand>("[\"Ah\",\"Ah\"]").is_err());
+        assert!(serde_json::from_str::<HandClass>("\"AAs\"").is_err());
+        assert!(serde_json::from_str::<TotalHand>("[\"Ah\",\"Ah\"]").is_err());
+    }
+
+    #[test]
+    fn hand_values_have_stable_tags() {
+        assert_eq!("\"two_pairs\"", serde_json::to_string(&HandRank::TwoPairs).unwrap());
+        let cards = [card(Suit::Spade, 1), card(Suit::Spade, 13), card(Suit::Spade, 12), card(Suit::Spade, 11), card(Suit::Spade, 10)];
+        let best = TotalHand::new(&cards).find_best_five_hand().unwrap();
+        let json = serde_json::to_string(&best.value()).unwrap();
+        assert_eq!("{\"hand_rank\":\"royal_flush\",\"card_ranks\":[\"A\",\"K\",\"Q\",\"J\",\"T\"]}", json);
+        assert_eq!(best.value(), serde_json::from_str::<HandValue>(&json).unwrap());
+        assert_eq!(best, serde_json::from_str(&serde_json::to_string(&best).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn board_in_progress_is_restored() {
+        let mut board = Board::new();
+        board.deal_starting_hands(2);
+        board.deal_cards_until(Phase::Flop);
+        let mut restored: Board = serde_json::from_str(&serde_json::to_string(&board).unwrap()).unwrap();
+        assert_eq!(board.cards(), restored.cards());
+        assert_eq!(board.remaining_cards(), restored.remaining_cards());
+        board.deal_cards_until(Phase::River);
+        restored.deal_cards_until(Phase::River);
+        assert_eq!(board.cards(), restored.cards());
+    }
+
+    #[test]
+    fn board_with_duplicate_cards_is_not_decoded() {
+        let json = "{\"cards\":[\"Ah\",\"Kd\",\"2c\"],\"deck\":[\"Ah\"]}";
+        assert!(serde_json::from_str::<Board>(json).is_err());
+    }
+}
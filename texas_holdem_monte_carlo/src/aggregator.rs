@@ -1,53 +1,304 @@
+use super::street::Outcome;
 use super::worker::TrialResult;
-use std::collections::HashMap;
-use texas_holdem::card::StartingHand;
+use texas_holdem::card::HandClass;
+use texas_holdem::position::Position;
 
+#[derive(Debug, PartialEq, Clone)]
 pub struct AggregationResult {
-    example_hand: StartingHand,
-    counts: HashMap<TrialResult, usize>
+    class: HandClass,
+    /// None for results of all positions.
+    position: Option<Position>,
+    /// Counts in order of TrialResult::all().
+    counts: [usize; TrialResult::COUNT],
+    /// Sum of chips won or lost in big blinds.
+    net_chips: f64,
 }
 
 impl AggregationResult {
-    pub fn new(example_hand: StartingHand) -> Self {
-        Self { example_hand, counts: HashMap::new() }
+    pub fn new(class: HandClass) -> Self {
+        Self { class, position: None, counts: [0; TrialResult::COUNT], net_chips: 0.0 }
     }
 
-    pub fn csv_head() -> String {
-        let cells = [
-            "hand",
-            "number_of_occurrence",
-            "pre_flop_win",
-            "flop_win",
-            "flop_drop",
-            "show_down_win",
-            "show_down_lose",
-        ];
-        cells.join(",").to_string()
+    pub fn new_at(class: HandClass, position: Position) -> Self {
+        Self { position: Some(position), ..Self::new(class) }
     }
 
     pub fn hand_summary(&self) -> String {
-        self.example_hand.summary()
+        self.class.summary()
+    }
+
+    pub fn hand_class(&self) -> HandClass {
+        self.class
+    }
+
+    pub fn position(&self) -> Option<Position> {
+        self.position
     }
 
     pub fn count_up(&mut self, result: TrialResult) {
-        let c = self.counts.entry(result).or_insert(0);
-        *c += 1;
+        self.add_count(result, 1);
+    }
+
+    pub fn add_count(&mut self, result: TrialResult, count: usize) {
+        self.counts[result.index()] += count;
+    }
+
+    pub fn count_of(&self, result: TrialResult) -> usize {
+        self.counts[result.index()]
     }
 
     pub fn total_count(&self) -> usize {
-        self.counts.values().fold(0, |sum, count| { sum + count })
-    }
-
-    pub fn csv_row(&self) -> String {
-        let cells = [
-            self.example_hand.summary(),
-            self.total_count().to_string(),
-            self.counts.get(&TrialResult::PreFlopWin).or(Some(&0)).unwrap().to_string(),
-            self.counts.get(&TrialResult::FlopWin).or(Some(&0)).unwrap().to_string(),
-            self.counts.get(&TrialResult::FlopDrop).or(Some(&0)).unwrap().to_string(),
-            self.counts.get(&TrialResult::ShowDownWin).or(Some(&0)).unwrap().to_string(),
-            self.counts.get(&TrialResult::ShowDownLose).or(Some(&0)).unwrap().to_string(),
-        ];
-        cells.join(",").to_string()
-    }
-}
\ No newline at end of file
+        self.counts.iter().sum()
+    }
+
+    pub fn net_chips(&self) -> f64 {
+        self.net_chips
+    }
+
+    pub fn add_net_chips(&mut self, chips: f64) {
+        self.net_chips += chips;
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        assert_eq!(self.class, other.class, "Results of different hands cannot be merged");
+        assert_eq!(self.position, other.position, "Results of different positions cannot be merged");
+        self.add_counts_of(other);
+    }
+
+    fn add_counts_of(&mut self, other: &Self) {
+        for (c, o) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *c += o;
+        }
+        self.net_chips += other.net_chips;
+    }
+
+    /// Wins on any street or at showdown. Split pots are not included.
+    pub fn win_count(&self) -> usize {
+        TrialResult::all().iter().filter(|r| r.is_win()).map(|r| self.count_of(*r)).sum()
+    }
+
+    pub fn show_down_count(&self) -> usize {
+        TrialResult::all().iter().filter(|r| r.is_show_down()).map(|r| self.count_of(*r)).sum()
+    }
+
+    /// Share of occurrences the hand won.
+    pub fn win_rate(&self) -> f64 {
+        ratio(self.win_count(), self.total_count())
+    }
+
+    /// Standard error of win_rate().
+    pub fn win_rate_std_error(&self) -> f64 {
+        let n = self.total_count();
+        if n == 0 {
+            return 0.0;
+        }
+        let p = self.win_rate();
+        (p * (1.0 - p) / n as f64).sqrt()
+    }
+
+    /// Share of showdowns the hand won. A split pot counts as half a win.
+    pub fn show_down_win_rate(&self) -> f64 {
+        let wins = self.count_of(TrialResult::ShowDownWin) as f64 + self.count_of(TrialResult::ShowDownSplit) as f64 / 2.0;
+        if self.show_down_count() == 0 { 0.0 } else { wins / self.show_down_count() as f64 }
+    }
+
+    /// Net units per occurrence when a win earns 1 unit, a showdown loss costs 1 unit,
+    /// and a split pot or a drop costs nothing.
+    pub fn ev_per_hand(&self) -> f64 {
+        let net = self.win_count() as f64 - self.count_of(TrialResult::ShowDownLose) as f64;
+        if self.total_count() == 0 { 0.0 } else { net / self.total_count() as f64 }
+    }
+
+    /// Big blinds won per 100 hands.
+    pub fn bb_per_100(&self) -> f64 {
+        if self.total_count() == 0 { 0.0 } else { 100.0 * self.net_chips / self.total_count() as f64 }
+    }
+}
+
+/// Results of all 169 hand classes indexed by HandClass::index(), results of them at each position,
+/// and the number of trials they came from.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AggregationTable {
+    results: Vec<AggregationResult>,
+    /// Indexed by HandClass::index() * Position::COUNT + Position::index().
+    position_results: Vec<AggregationResult>,
+    trial_count: usize,
+}
+
+impl Default for AggregationTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregationTable {
+    pub fn new() -> Self {
+        let classes = HandClass::all();
+        Self {
+            results: classes.iter().copied().map(AggregationResult::new).collect(),
+            position_results: classes.iter()
+                .flat_map(|c| Position::all().iter().map(move |p| AggregationResult::new_at(*c, *p)).collect::<Vec<_>>())
+                .collect(),
+            trial_count: 0,
+        }
+    }
+
+    fn position_index(class: HandClass, position: Position) -> usize {
+        class.index() * Position::COUNT + position.index()
+    }
+
+    pub fn record(&mut self, outcome: &Outcome) {
+        let class = outcome.hand.class();
+        for r in [&mut self.results[class.index()], &mut self.position_results[Self::position_index(class, outcome.position)]] {
+            r.count_up(outcome.result);
+            r.add_net_chips(outcome.net);
+        }
+    }
+
+    /// Adds counts of a result of a position to the result of the position and the result of all positions.
+    pub fn add_result(&mut self, result: &AggregationResult) {
+        let position = result.position.expect("Only results of a position can be added");
+        self.results[result.class.index()].add_counts_of(result);
+        self.position_results[Self::position_index(result.class, position)].merge(result);
+    }
+
+    pub fn get(&self, class: HandClass) -> &AggregationResult {
+        &self.results[class.index()]
+    }
+
+    pub fn get_at(&self, class: HandClass, position: Position) -> &AggregationResult {
+        &self.position_results[Self::position_index(class, position)]
+    }
+
+    /// Results of hands which occurred at least once, in order of the hand grid.
+    pub fn occurred_results(&self) -> Vec<&AggregationResult> {
+        self.results.iter().filter(|r| r.total_count() > 0).collect()
+    }
+
+    /// Results of hands at positions which occurred at least once, in order of the hand grid and then positions.
+    pub fn occurred_position_results(&self) -> Vec<&AggregationResult> {
+        self.position_results.iter().filter(|r| r.total_count() > 0).collect()
+    }
+
+    pub fn trial_count(&self) -> usize {
+        self.trial_count
+    }
+
+    pub fn add_trials(&mut self, count: usize) {
+        self.trial_count += count;
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (r, o) in self.results.iter_mut().zip(other.results.iter()) {
+            r.merge(o);
+        }
+        for (r, o) in self.position_results.iter_mut().zip(other.position_results.iter()) {
+            r.merge(o);
+        }
+        self.trial_count += other.trial_count;
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 { 0.0 } else { numerator as f64 / denominator as f64 }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+    use texas_holdem::card::StartingHand;
+
+    fn aces() -> HandClass {
+        HandClass::from_summary("AA").unwrap()
+    }
+
+    #[test]
+    fn rates_are_zero_without_occurrence() {
+        let result = AggregationResult::new(aces());
+        assert_eq!(0.0, result.win_rate());
+        assert_eq!(0.0, result.win_rate_std_error());
+        assert_eq!(0.0, result.show_down_win_rate());
+        assert_eq!(0.0, result.ev_per_hand());
+        assert_eq!(0.0, result.bb_per_100());
+    }
+
+    #[test]
+    fn rates_are_derived_from_counts() {
+        let mut result = AggregationResult::new(aces());
+        result.count_up(TrialResult::PreFlopWin);
+        result.count_up(TrialResult::ShowDownWin);
+        result.count_up(TrialResult::ShowDownLose);
+        result.count_up(TrialResult::FlopDrop);
+        assert_eq!(4, result.total_count());
+        assert_eq!(0.5, result.win_rate());
+        assert_eq!(0.25, result.win_rate_std_error());
+        assert_eq!(0.5, result.show_down_win_rate());
+        assert_eq!(0.25, result.ev_per_hand());
+        result.add_net_chips(-3.0);
+        assert_eq!(-75.0, result.bb_per_100());
+    }
+
+    #[test]
+    fn table_records_by_class_and_position() {
+        let mut table = AggregationTable::new();
+        let hand = StartingHand::new(
+            NonJokerCard::new(Suit::Spade, CardRank::Ace),
+            NonJokerCard::new(Suit::Heart, CardRank::Ace),
+        );
+        table.record(&Outcome { hand, position: Position::Button, result: TrialResult::ShowDownWin, net: 6.0 });
+        table.record(&Outcome { hand, position: Position::BigBlind, result: TrialResult::PreFlopWin, net: 0.5 });
+        assert_eq!(1, table.get(aces()).count_of(TrialResult::ShowDownWin));
+        assert_eq!(6.5, table.get(aces()).net_chips());
+        assert_eq!(1, table.get_at(aces(), Position::Button).count_of(TrialResult::ShowDownWin));
+        assert_eq!(50.0, table.get_at(aces(), Position::BigBlind).bb_per_100());
+        assert_eq!(1, table.occurred_results().len());
+        assert_eq!(2, table.occurred_position_results().len());
+    }
+
+    #[test]
+    fn occurred_results_are_in_order_of_grid_and_positions() {
+        let mut table = AggregationTable::new();
+        for (summary, position) in [("72o", Position::UnderTheGun), ("AA", Position::Button), ("AA", Position::UnderTheGun)].iter() {
+            let mut result = AggregationResult::new_at(HandClass::from_summary(summary).unwrap(), *position);
+            result.count_up(TrialResult::FlopDrop);
+            table.add_result(&result);
+        }
+        let summaries: Vec<String> = table.occurred_results().iter().map(|r| r.hand_summary()).collect();
+        assert_eq!(vec!["AA", "72o"], summaries);
+        let positions: Vec<Option<Position>> = table.occurred_position_results().iter().map(|r| r.position()).collect();
+        assert_eq!(vec![Some(Position::UnderTheGun), Some(Position::Button), Some(Position::UnderTheGun)], positions);
+    }
+
+    #[test]
+    fn tables_are_merged() {
+        let mut result = AggregationResult::new_at(aces(), Position::CutOff);
+        result.add_count(TrialResult::FlopWin, 2);
+        let mut a = AggregationTable::new();
+        a.add_result(&result);
+        a.add_trials(3);
+        let mut b = AggregationTable::new();
+        b.add_result(&result);
+        b.add_result(&result);
+        b.add_trials(4);
+        a.merge(&b);
+        assert_eq!(6, a.get(aces()).count_of(TrialResult::FlopWin));
+        assert_eq!(6, a.get_at(aces(), Position::CutOff).count_of(TrialResult::FlopWin));
+        assert_eq!(7, a.trial_count());
+    }
+
+    #[test]
+    #[should_panic(expected = "different hands")]
+    fn results_of_different_hands_are_not_merged() {
+        let mut a = AggregationResult::new(aces());
+        a.merge(&AggregationResult::new(HandClass::from_summary("KK").unwrap()));
+    }
+
+    #[test]
+    #[should_panic(expected = "different positions")]
+    fn results_of_different_positions_are_not_merged() {
+        let mut a = AggregationResult::new_at(aces(), Position::Button);
+        a.merge(&AggregationResult::new_at(aces(), Position::SmallBlind));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::aggregator::{ AggregationResult, AggregationTable };
+use crate::street::BetModel;
+use crate::worker::TrialResult;
+use std::collections::BTreeSet;
+use std::fs::{ self, File };
+use std::io::{ self, BufRead, BufReader, BufWriter, Write };
+use texas_holdem::card::HandClass;
+use texas_holdem::position::Position;
+
+const CHECKPOINT_VERSION: u32 = 5;
+
+/// Aggregated results of a simulation so far, which can be saved to and resumed from a checkpoint file.
+pub struct SimulationState {
+    pub seed: u64,
+    pub num_of_players: usize,
+    /// Name of the strategy given by the option.
+    pub strategy: String,
+    pub bets: BetModel,
+    pub table: AggregationTable,
+    /// Indexes of the parts of trials whose results are in the table.
+    /// They may have gaps because parts run on threads finish in any order.
+    pub finished_parts: BTreeSet<usize>,
+}
+
+impl SimulationState {
+    pub fn new(seed: u64, num_of_players: usize, strategy: &str, bets: BetModel) -> Self {
+        Self {
+            seed,
+            num_of_players,
+            strategy: String::from(strategy),
+            bets,
+            table: AggregationTable::new(),
+            finished_parts: BTreeSet::new(),
+        }
+    }
+
+    /// Merges results of the part of trials with given index.
+    pub fn finish_part(&mut self, index: usize, results: &AggregationTable) {
+        assert!(self.finished_parts.insert(index), "Part {} is already finished", index);
+        self.table.merge(results);
+    }
+
+    /// Returns an error naming the setting if the results so far were made with another strategy or bet model,
+    /// because they cannot be merged with new results.
+    pub fn check_settings(&self, strategy: &str, bets: &BetModel) -> io::Result<()> {
+        let mismatch = |field: &str, saved: &str, given: &str| io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("The checkpoint was made with {} {} but {} is given", field, saved, given),
+        );
+        if self.strategy != strategy {
+            return Err(mismatch("strategy", &self.strategy, strategy));
+        }
+        if self.bets != *bets {
+            return Err(mismatch("bets", &self.bets.spec(), &bets.spec()));
+        }
+        Ok(())
+    }
+
+    pub fn completed_trials(&self) -> usize {
+        self.table.trial_count()
+    }
+
+    /// Writes the state to a temporary file first and then renames it,
+    /// so that an existing checkpoint is not broken by an interruption while saving.
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let temporary_path = format!("{}.tmp", path);
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(temporary_path, path)
+    }
+
+    pub fn load(path: &str) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "checkpoint,{}", CHECKPOINT_VERSION)?;
+        writeln!(writer, "seed,{}", self.seed)?;
+        writeln!(writer, "num_of_players,{}", self.num_of_players)?;
+        writeln!(writer, "strategy,{}", self.strategy)?;
+        writeln!(writer, "bets,{}", self.bets.spec())?;
+        writeln!(writer, "completed_trials,{}", self.completed_trials())?;
+        let parts: Vec<String> = self.finished_parts.iter().map(|p| p.to_string()).collect();
+        writeln!(writer, "finished_parts,{}", parts.join(","))?;
+        // results of all positions are the sums of them
+        for r in self.table.occurred_position_results() {
+            let counts: Vec<String> = TrialResult::all().iter().map(|t| r.count_of(*t).to_string()).collect();
+            let position = r.position().expect("position result has a position");
+            writeln!(writer, "{},{},{},{}", r.hand_summary(), position.name(), counts.join(","), r.net_chips())?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = reader.lines();
+        let mut header = |key: &str| -> io::Result<String> {
+            let line = lines.next().unwrap_or_else(|| Err(invalid(String::from("Unexpected end of file"))))?;
+            line.strip_prefix(&format!("{},", key))
+                .map(String::from)
+                .ok_or_else(|| invalid(format!("{} is expected: {}", key, line)))
+        };
+        let parse_number = |s: String| s.parse::<u64>().map_err(|_| invalid(format!("Invalid number: {}", s)));
+
+        let version = header("checkpoint")?;
+        if version != CHECKPOINT_VERSION.to_string() {
+            return Err(invalid(format!("Unsupported checkpoint version: {}", version)));
+        }
+        let seed = parse_number(header("seed")?)?;
+        let num_of_players = parse_number(header("num_of_players")?)? as usize;
+        let strategy = header("strategy")?;
+        let bets_spec = header("bets")?;
+        let bets = BetModel::from_spec(&bets_spec).ok_or_else(|| invalid(format!("Invalid bets: {}", bets_spec)))?;
+        let completed_trials = parse_number(header("completed_trials")?)? as usize;
+        let finished_parts = header("finished_parts")?;
+
+        let mut state = Self::new(seed, num_of_players, &strategy, bets);
+        state.table.add_trials(completed_trials);
+        for part in finished_parts.split(',').filter(|p| !p.is_empty()) {
+            state.finished_parts.insert(parse_number(String::from(part))? as usize);
+        }
+        for line in lines {
+            let line = line?;
+            let columns: Vec<&str> = line.split(',').collect();
+            if columns.len() != 3 + TrialResult::COUNT {
+                return Err(invalid(format!("Invalid number of columns: {}", line)));
+            }
+            let class = HandClass::from_summary(columns[0])
+                .ok_or_else(|| invalid(format!("Invalid hand: {}", columns[0])))?;
+            let position = Position::from_name(columns[1])
+                .ok_or_else(|| invalid(format!("Invalid position: {}", columns[1])))?;
+            let mut result = AggregationResult::new_at(class, position);
+            for (t, c) in TrialResult::all().iter().zip(columns[2 ..].iter()) {
+                result.add_count(*t, parse_number(c.to_string())? as usize);
+            }
+            let net_chips = columns[columns.len() - 1];
+            result.add_net_chips(net_chips.parse().map_err(|_| invalid(format!("Invalid number: {}", net_chips)))?);
+            state.table.add_result(&result);
+        }
+        Ok(state)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample_state() -> SimulationState {
+        let mut state = SimulationState::new(42, 6, "calling_station", BetModel::from_spec("2,1,2,3").unwrap());
+        state.table.add_trials(10);
+        state.finished_parts.extend([0, 2]);
+        for (summary, count) in [("AA", 3), ("72o", 5)].iter() {
+            let mut result = AggregationResult::new_at(HandClass::from_summary(summary).unwrap(), Position::Button);
+            result.add_count(TrialResult::ShowDownWin, *count);
+            result.add_count(TrialResult::FlopDrop, 1);
+            result.add_net_chips(0.1 * *count as f64);
+            state.table.add_result(&result);
+        }
+        state
+    }
+
+    #[test]
+    fn written_state_can_be_read() {
+        let state = sample_state();
+        let mut buffer = Vec::new();
+        state.write(&mut buffer).unwrap();
+        let read = SimulationState::read(&buffer[..]).unwrap();
+        assert_eq!(42, read.seed);
+        assert_eq!(6, read.num_of_players);
+        assert_eq!("calling_station", read.strategy);
+        assert_eq!("2,1,2,3", read.bets.spec());
+        assert_eq!(10, read.completed_trials());
+        assert_eq!(vec![0, 2], read.finished_parts.iter().copied().collect::<Vec<usize>>());
+        assert_eq!(state.table, read.table);
+        let aces = read.table.get(HandClass::from_summary("AA").unwrap());
+        assert_eq!(3, aces.count_of(TrialResult::ShowDownWin));
+        assert_eq!(1, aces.count_of(TrialResult::FlopDrop));
+        assert_eq!(0.1 * 3.0, read.table.get_at(aces.hand_class(), Position::Button).net_chips());
+    }
+
+    #[test]
+    fn resume_with_other_settings_is_rejected() {
+        let state = sample_state();
+        assert!(state.check_settings("calling_station", &BetModel::from_spec("2,1,2,3").unwrap()).is_ok());
+        let error = state.check_settings("default", &state.bets).unwrap_err();
+        assert!(error.to_string().contains("strategy"));
+        let error = state.check_settings("calling_station", &BetModel::default()).unwrap_err();
+        assert!(error.to_string().contains("bets"));
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let text = "checkpoint,0\nseed,1\nnum_of_players,6\nstrategy,default\nbets,2,2,4,4\ncompleted_trials,0\nfinished_parts,\n";
+        assert!(SimulationState::read(text.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn broken_row_is_rejected() {
+        let text = "checkpoint,5\nseed,1\nnum_of_players,6\nstrategy,default\nbets,2,2,4,4\ncompleted_trials,0\nfinished_parts,\nAA,BTN,1,2\n";
+        assert!(SimulationState::read(text.as_bytes()).is_err());
+    }
+}
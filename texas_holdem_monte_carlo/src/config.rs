@@ -0,0 +1,201 @@
+use crate::grid;
+use crate::output::OutputFormat;
+use crate::street::{ self, BetModel };
+use texas_holdem::position::Position;
+
+pub const DEFAULT_WORKER_COUNT: usize = 8;
+
+/// Settings of a simulation given by command line options.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SimulationConfig {
+    pub worker_count: usize,
+    pub trial_per_worker: usize,
+    pub num_of_players: usize,
+    /// Name of the strategy deciding whether players continue on each street.
+    pub strategy: String,
+    pub bets: BetModel,
+    /// Seed of the first part of trials. Part i of 1024 trials uses seed + i.
+    pub seed: Option<u64>,
+    pub format: OutputFormat,
+    /// Results are written to stdout if not given.
+    pub output_path: Option<String>,
+    /// Writes results of each hand at each position instead of results of each hand if true.
+    pub by_position: bool,
+    /// Metric shown on the 13x13 hand grid.
+    pub grid_metric: String,
+    /// Prints the grid to stderr if true.
+    pub show_grid: bool,
+    /// Shows results at this position on the grid if given, or results of all positions.
+    pub grid_position: Option<Position>,
+    /// Writes the grid as an HTML heatmap if given.
+    pub html_path: Option<String>,
+    /// Saves aggregated results periodically to this file if given.
+    pub checkpoint_path: Option<String>,
+    pub checkpoint_interval_secs: u64,
+    /// Resumes from this checkpoint file if given.
+    pub resume_path: Option<String>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            worker_count: DEFAULT_WORKER_COUNT,
+            trial_per_worker: 500_000,
+            num_of_players: 6,
+            strategy: String::from("default"),
+            bets: BetModel::default(),
+            seed: None,
+            format: OutputFormat::Csv,
+            output_path: None,
+            by_position: false,
+            grid_metric: String::from("win_rate"),
+            show_grid: false,
+            grid_position: None,
+            html_path: None,
+            checkpoint_path: None,
+            checkpoint_interval_secs: 60,
+            resume_path: None,
+        }
+    }
+}
+
+pub const USAGE: &str = "Options: [--workers N] [--trials N (per worker)] [--players N] [--strategy default|calling_station] [--bets PREFLOP,FLOP,TURN,RIVER (big blinds)] [--seed N] [--format csv|json|jsonl] [--output PATH] [--by-position] [--grid METRIC] [--grid-position POS] [--html PATH] [--checkpoint PATH] [--checkpoint-interval SECS] [--resume PATH]";
+
+impl SimulationConfig {
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut iter = args.iter();
+        while let Some(option) = iter.next() {
+            if option == "--by-position" {
+                config.by_position = true;
+                continue;
+            }
+            let value = iter.next().ok_or_else(|| format!("Value of {} is missing", option))?;
+            match option.as_str() {
+                "--workers" => config.worker_count = parse_number(option, value)?,
+                "--trials" => config.trial_per_worker = parse_number(option, value)?,
+                "--players" => config.num_of_players = parse_number(option, value)?,
+                "--strategy" => config.strategy = value.clone(),
+                "--bets" => {
+                    config.bets = BetModel::from_spec(value)
+                        .ok_or_else(|| format!("Invalid bets: {}", value))?;
+                },
+                "--seed" => config.seed = Some(parse_number(option, value)?),
+                "--format" => {
+                    config.format = OutputFormat::from_name(value)
+                        .ok_or_else(|| format!("Unknown format: {}", value))?;
+                },
+                "--output" => config.output_path = Some(value.clone()),
+                "--grid" => {
+                    config.grid_metric = value.clone();
+                    config.show_grid = true;
+                },
+                "--grid-position" => {
+                    config.grid_position = Some(Position::from_name(value)
+                        .ok_or_else(|| format!("Unknown position: {}", value))?);
+                },
+                "--html" => config.html_path = Some(value.clone()),
+                "--checkpoint" => config.checkpoint_path = Some(value.clone()),
+                "--checkpoint-interval" => config.checkpoint_interval_secs = parse_number(option, value)?,
+                "--resume" => config.resume_path = Some(value.clone()),
+                _ => return Err(format!("Unknown option: {}", option)),
+            }
+        }
+        if config.checkpoint_path.is_none() {
+            // keep saving to the file resumed from
+            config.checkpoint_path = config.resume_path.clone();
+        }
+        if street::strategy_by_name(&config.strategy).is_none() {
+            return Err(format!("Unknown strategy: {}", config.strategy));
+        }
+        if grid::metric_by_name(&config.grid_metric).is_none() {
+            return Err(format!("Unknown metric: {}", config.grid_metric));
+        }
+        if config.worker_count == 0 {
+            return Err(String::from("The number of workers must be 1 or more"));
+        }
+        if config.trial_per_worker == 0 {
+            return Err(String::from("The number of trials must be 1 or more"));
+        }
+        if !(2 ..= 10).contains(&config.num_of_players) {
+            return Err(String::from("The number of players must be 2 to 10"));
+        }
+        if let Some(position) = config.grid_position {
+            if !Position::of_table(config.num_of_players).contains(&position) {
+                return Err(format!("{} is not on a table of {} players", position.name(), config.num_of_players));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Total number of trials of all workers.
+    pub fn trial_count(&self) -> usize {
+        self.worker_count * self.trial_per_worker
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Value of {} must be a number: {}", option, value))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn default_is_used_without_options() {
+        assert_eq!(Ok(SimulationConfig::default()), SimulationConfig::from_args(&[]));
+    }
+
+    #[test]
+    fn options_are_parsed() {
+        let config = SimulationConfig::from_args(&args("--seed 42 --players 9 --format jsonl --output out.jsonl")).unwrap();
+        assert_eq!(Some(42), config.seed);
+        assert_eq!(9, config.num_of_players);
+        assert_eq!(OutputFormat::JsonLines, config.format);
+        assert_eq!(Some(String::from("out.jsonl")), config.output_path);
+    }
+
+    #[test]
+    fn resumed_file_is_used_as_checkpoint() {
+        let config = SimulationConfig::from_args(&args("--resume run.ckpt")).unwrap();
+        assert_eq!(Some(String::from("run.ckpt")), config.checkpoint_path);
+    }
+
+    #[test]
+    fn grid_options_are_parsed() {
+        let config = SimulationConfig::from_args(&args("--grid ev_per_hand --html grid.html")).unwrap();
+        assert_eq!("ev_per_hand", config.grid_metric);
+        assert!(config.show_grid);
+        assert_eq!(Some(String::from("grid.html")), config.html_path);
+    }
+
+    #[test]
+    fn position_options_are_parsed() {
+        let config = SimulationConfig::from_args(&args("--by-position --bets 3,2,4,8 --grid-position BTN")).unwrap();
+        assert!(config.by_position);
+        assert_eq!(BetModel::from_spec("3,2,4,8").unwrap(), config.bets);
+        assert_eq!(Some(Position::Button), config.grid_position);
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        assert!(SimulationConfig::from_args(&args("--seed")).is_err());
+        assert!(SimulationConfig::from_args(&args("--seed x")).is_err());
+        assert!(SimulationConfig::from_args(&args("--format xml")).is_err());
+        assert!(SimulationConfig::from_args(&args("--players 11")).is_err());
+        assert!(SimulationConfig::from_args(&args("--workers 0")).is_err());
+        assert!(SimulationConfig::from_args(&args("--trials 0")).is_err());
+        assert!(SimulationConfig::from_args(&args("--foo 1")).is_err());
+        assert!(SimulationConfig::from_args(&args("--grid foo")).is_err());
+        assert!(SimulationConfig::from_args(&args("--strategy foo")).is_err());
+        assert!(SimulationConfig::from_args(&args("--bets 2,2")).is_err());
+        assert!(SimulationConfig::from_args(&args("--grid-position XX")).is_err());
+        assert!(SimulationConfig::from_args(&args("--players 6 --grid-position MP")).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use texas_holdem::card::HandClass;
+use texas_holdem::equity::{ self, PreFlopEquityTable, MAX_OPPONENTS };
+use texas_holdem::parallel::Executor;
+
+struct EquityRow {
+    class: HandClass,
+    against_random: [f64; MAX_OPPONENTS],
+    /// Heads-up equities against classes whose indexes are the same or larger.
+    heads_up: Vec<f64>,
+}
+
+/// Generates the pre-flop equity table with given number of trials per value.
+pub fn generate(worker_count: usize, trials: usize) -> PreFlopEquityTable {
+    let mut against_random = vec![[0.0; MAX_OPPONENTS]; HandClass::COUNT];
+    let mut heads_up = vec![0.0; HandClass::COUNT * HandClass::COUNT];
+    Executor::new(worker_count).for_each_ordered(
+        0 .. HandClass::COUNT,
+        |i| calculate_row(HandClass::from_index(i), trials),
+        |row| {
+            let i = row.class.index();
+            eprintln!("{} ({}/{})", row.class, i + 1, HandClass::COUNT);
+            against_random[i] = row.against_random;
+            for (k, e) in row.heads_up.iter().enumerate() {
+                let j = i + k;
+                heads_up[i * HandClass::COUNT + j] = *e;
+                heads_up[j * HandClass::COUNT + i] = 1.0 - *e;
+            }
+        },
+    );
+    PreFlopEquityTable::new(trials, against_random, heads_up)
+}
+
+fn calculate_row(class: HandClass, trials: usize) -> EquityRow {
+    let hand = class.starting_hands()[0];
+    let mut against_random = [0.0; MAX_OPPONENTS];
+    for (n, e) in against_random.iter_mut().enumerate() {
+        *e = equity::sample_equity_against_random(hand, n + 1, trials);
+    }
+    let heads_up = (class.index() .. HandClass::COUNT)
+        .map(|j| {
+            let opponent = HandClass::from_index(j);
+            // equity against the same class is exactly even
+            if opponent == class { 0.5 } else { equity::sample_equity_of_classes(class, opponent, trials) }
+        })
+        .collect();
+    EquityRow { class, against_random, heads_up }
+}
@@ -1,39 +1,31 @@
-use playing_card::card::NonJokerCard;
-use std::cmp::Ordering;
-use texas_holdem::card::cmp_cards;
-use texas_holdem::card::cmp_card_ranks;
-use texas_holdem::card::{ HandRank, StartingHand, TotalHand, };
+use texas_holdem::card::{ Board, DrawKind, Draws, MadeHand, MadeHandCategory, StartingHand };
 
-pub fn evaluate_hand(hand: StartingHand, flop: [NonJokerCard; 3]) -> bool {
-    let greatest_flop_rank = flop.iter().max_by(|a, b| cmp_cards(**a, **b)).unwrap().rank();
-
-    let over_card_count = hand.both_cards().iter().filter(|c| 
-        cmp_card_ranks(c.rank(), greatest_flop_rank) != Ordering::Less
-    ).collect::<Vec<&NonJokerCard>>().len();
+pub fn evaluate_hand(hand: StartingHand, board: &Board) -> bool {
+    let made_hand = MadeHand::classify(hand, board);
+    match made_hand.category() {
+        MadeHandCategory::HighCard | MadeHandCategory::BoardPlays => (),
+        _ => return true,
+    }
 
+    let over_card_count = made_hand.overcard_count();
     if over_card_count >= 2 {
         return true;
     }
 
-    let total_hand = TotalHand::new(&[
-        hand.higher_card(),
-        hand.lower_card(),
-        flop[0],
-        flop[1],
-        flop[2],
-    ]);
-    
-    if total_hand.straight_draw_ranks().len() >= 2 || total_hand.is_flush_draw() {
-        return true;
-    }
+    let draws = Draws::classify(hand, board);
+    let has_draw_of = |kinds: &[DrawKind]| {
+        draws.all().iter().any(|d| d.uses_hole_cards() && kinds.contains(&d.kind()))
+    };
 
-    if over_card_count == 1 && total_hand.straight_draw_ranks().len() == 1 {
+    let strong_draws = [
+        DrawKind::NutFlushDraw,
+        DrawKind::FlushDraw,
+        DrawKind::OpenEndedStraightDraw,
+        DrawKind::DoubleGutshot,
+    ];
+    if has_draw_of(&strong_draws) {
         return true;
     }
-    
-    let best = total_hand.find_best_five_hand().unwrap();
-    match best.hand_rank() {
-        HandRank::HighCard => false,
-        _ => true,
-    }
-}
\ No newline at end of file
+
+    over_card_count == 1 && has_draw_of(&[DrawKind::Gutshot])
+}
@@ -0,0 +1,228 @@
+use crate::aggregator::AggregationResult;
+use texas_holdem::card::{ HandClass, StartingHand };
+
+/// Values of a metric placed on the 13x13 hand grid.
+/// Pairs are on the diagonal, suited hands are above it and offsuit hands are below it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandGrid {
+    values: Vec<Option<f64>>,
+}
+
+/// Metrics of AggregationResult which can be shown on the grid.
+pub fn metric_by_name(name: &str) -> Option<fn(&AggregationResult) -> f64> {
+    let metric: fn(&AggregationResult) -> f64 = match name {
+        "number_of_occurrence" => |r| r.total_count() as f64,
+        "win_rate" => AggregationResult::win_rate,
+        "show_down_win_rate" => AggregationResult::show_down_win_rate,
+        "ev_per_hand" => AggregationResult::ev_per_hand,
+        "bb_per_100" => AggregationResult::bb_per_100,
+        _ => return None,
+    };
+    Some(metric)
+}
+
+impl Default for HandGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HandGrid {
+    /// Create a grid without any values.
+    pub fn new() -> Self {
+        Self { values: vec![None; HandClass::COUNT] }
+    }
+
+    pub fn from_fn<F: Fn(HandClass) -> Option<f64>>(f: F) -> Self {
+        Self { values: HandClass::all().into_iter().map(f).collect() }
+    }
+
+    pub fn from_results(results: &[&AggregationResult], metric: fn(&AggregationResult) -> f64) -> Self {
+        let mut grid = Self::new();
+        for r in results {
+            grid.set(r.hand_class(), metric(r));
+        }
+        grid
+    }
+
+    pub fn set(&mut self, class: HandClass, value: f64) {
+        self.values[class.index()] = Some(value);
+    }
+
+    pub fn set_hand(&mut self, hand: StartingHand, value: f64) {
+        self.set(hand.class(), value);
+    }
+
+    pub fn get(&self, class: HandClass) -> Option<f64> {
+        self.values[class.index()]
+    }
+
+    /// The smallest and the largest values. None if the grid is empty.
+    pub fn range(&self) -> Option<(f64, f64)> {
+        self.values.iter().flatten().fold(None, |acc, v| match acc {
+            None => Some((*v, *v)),
+            Some((min, max)) => Some((min.min(*v), max.max(*v))),
+        })
+    }
+
+    /// Position of the value between the smallest and the largest values, from 0 to 1.
+    fn scale(&self, value: f64) -> f64 {
+        match self.range() {
+            Some((min, max)) if max > min => (value - min) / (max - min),
+            _ => 0.5,
+        }
+    }
+
+    /// Renders the grid as text lines. Cell backgrounds are colored with ANSI escape codes if `colored` is true.
+    pub fn render_terminal(&self, colored: bool) -> String {
+        let mut text = String::new();
+        for row in 0 .. 13 {
+            let mut cells = Vec::new();
+            for column in 0 .. 13 {
+                let class = HandClass::from_grid_position(row, column);
+                let cell = format!("{:<4}{:>6}", class.summary(), format_value(self.get(class)));
+                match self.get(class) {
+                    Some(v) if colored => {
+                        let (r, g, b) = heat_color(self.scale(v));
+                        cells.push(format!("\x1b[48;2;{};{};{}m\x1b[30m{}\x1b[0m", r, g, b, cell));
+                    },
+                    _ => cells.push(cell),
+                }
+            }
+            text.push_str(&cells.join(" "));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Renders the grid as a self-contained HTML document.
+    pub fn render_html(&self, title: &str) -> String {
+        let mut rows = String::new();
+        for row in 0 .. 13 {
+            rows.push_str("<tr>");
+            for column in 0 .. 13 {
+                let class = HandClass::from_grid_position(row, column);
+                let style = match self.get(class) {
+                    Some(v) => {
+                        let (r, g, b) = heat_color(self.scale(v));
+                        format!("background:rgb({},{},{})", r, g, b)
+                    },
+                    None => String::from("background:#ddd"),
+                };
+                rows.push_str(&format!(
+                    "<td style=\"{}\" title=\"{}: {}\"><b>{}</b><br>{}</td>",
+                    style, class, format_value(self.get(class)), class, format_value(self.get(class))
+                ));
+            }
+            rows.push_str("</tr>\n");
+        }
+        let legend = match self.range() {
+            Some((min, max)) => format!("<p>min {} / max {}</p>", format_value(Some(min)), format_value(Some(max))),
+            None => String::new(),
+        };
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+             table {{ border-collapse: collapse; font-family: monospace; }}\n\
+             td {{ width: 4em; height: 3em; text-align: center; border: 1px solid #fff; font-size: 12px; }}\n\
+             </style>\n</head>\n<body>\n<h1>{title}</h1>\n<table>\n{rows}</table>\n{legend}\n</body>\n</html>\n",
+            title = escape_html(title), rows = rows, legend = legend,
+        )
+    }
+}
+
+fn format_value(value: Option<f64>) -> String {
+    match value {
+        Some(v) => format!("{:.3}", v),
+        None => String::from("-"),
+    }
+}
+
+/// Red for 0, yellow for 0.5 and green for 1.
+fn heat_color(t: f64) -> (u8, u8, u8) {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        (230, (230.0 * t * 2.0).round() as u8, 80)
+    } else {
+        ((230.0 * (2.0 - t * 2.0)).round() as u8, 230, 80)
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+     .replace('<', "&lt;")
+     .replace('>', "&gt;")
+     .replace('"', "&quot;")
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::worker::TrialResult;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+
+    fn class(summary: &str) -> HandClass {
+        HandClass::from_summary(summary).unwrap()
+    }
+
+    #[test]
+    fn values_are_placed_by_class() {
+        let hand = StartingHand::new(
+            NonJokerCard::new(Suit::Spade, CardRank::Ace),
+            NonJokerCard::new(Suit::Spade, CardRank::King),
+        );
+        let mut result = AggregationResult::new(hand.class());
+        result.count_up(TrialResult::ShowDownWin);
+        let grid = HandGrid::from_results(&[&result], metric_by_name("win_rate").unwrap());
+        assert_eq!(Some(1.0), grid.get(class("AKs")));
+        assert_eq!(None, grid.get(class("AKo")));
+    }
+
+    #[test]
+    fn range_is_none_for_empty_grid() {
+        assert_eq!(None, HandGrid::new().range());
+    }
+
+    #[test]
+    fn range_covers_all_values() {
+        let grid = HandGrid::from_fn(|c| Some(c.index() as f64));
+        assert_eq!(Some((0.0, 168.0)), grid.range());
+    }
+
+    #[test]
+    fn terminal_table_has_13_rows_in_grid_order() {
+        let mut grid = HandGrid::new();
+        grid.set(class("AA"), 0.85);
+        let text = grid.render_terminal(false);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(13, lines.len());
+        assert!(lines[0].starts_with("AA   0.850 AKs      -"));
+        assert!(lines[12].ends_with("22       -"));
+    }
+
+    #[test]
+    fn colored_terminal_table_has_escape_codes() {
+        let grid = HandGrid::from_fn(|c| Some(c.index() as f64));
+        assert!(grid.render_terminal(true).contains("\x1b[48;2;230;0;80m"));
+    }
+
+    #[test]
+    fn html_has_all_cells() {
+        let grid = HandGrid::from_fn(|c| Some(c.index() as f64));
+        let html = grid.render_html("<win rate>");
+        assert_eq!(169, html.matches("<td").count());
+        assert!(html.contains("<title>&lt;win rate&gt;</title>"));
+    }
+
+    #[test]
+    fn color_changes_from_red_to_green() {
+        assert_eq!((230, 0, 80), heat_color(0.0));
+        assert_eq!((230, 230, 80), heat_color(0.5));
+        assert_eq!((0, 230, 80), heat_color(1.0));
+    }
+
+    #[test]
+    fn unknown_metric_is_none() {
+        assert!(metric_by_name("foo").is_none());
+    }
+}
@@ -1,42 +1,164 @@
 extern crate playing_card;
+extern crate ctrlc;
+extern crate rand;
 extern crate texas_holdem;
+extern crate texas_holdem_solver;
 
 pub mod aggregator;
+pub mod checkpoint;
+pub mod config;
+pub mod equity_table;
 pub mod flop_hand;
+pub mod grid;
+pub mod output;
+pub mod progress;
+pub mod simulation;
 pub mod starting_hand;
+pub mod street;
 pub mod worker;
 
-use aggregator::{ AggregationResult };
-use std::collections::HashMap;
-use std::thread;
-use std::sync::mpsc;
+use config::SimulationConfig;
+use grid::HandGrid;
+use output::Metadata;
+use std::env;
+use std::fs::File;
+use std::io::{ self, BufWriter, IsTerminal, Write };
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+use texas_holdem::equity::PreFlopEquityTable;
+use texas_holdem_solver::chart::{ PushFoldChart, PushFoldConfig };
 
 fn main() {
-    let worker_count = 8;
-    let trial_per_worker = 500_000;
-    let num_of_players = 6;
-    
-    let (tx, rx) = mpsc::channel();
-    for _ in 1 ..= worker_count {
-        let tx_clone = tx.clone();
-        thread::spawn(move || {
-            worker::run_worker(tx_clone, num_of_players, trial_per_worker);
-        });
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(|s| s.as_str()) {
+        Some("equity-table") => generate_equity_table(&args[2 ..]),
+        Some("push-fold") => print_push_fold_charts(&args[2 ..]),
+        None | Some("simulate") => run_simulation(&args[args.len().min(2) ..]),
+        Some(option) if option.starts_with("--") => run_simulation(&args[1 ..]),
+        Some(command) => {
+            eprintln!("Unknown command: {}", command);
+            process::exit(2);
+        }
     }
-    drop(tx);
-
-    let mut aggregations = HashMap::new();
-    for received in rx {
-        let starting_hand = received.starting_hand();
-        let count = aggregations.entry(starting_hand.summary())
-                                .or_insert(AggregationResult::new(starting_hand));
-        count.count_up(received.result());
+}
+
+/// Usage: equity-table <output path> [trials per value]
+fn generate_equity_table(args: &[String]) {
+    let path = args.first().expect("Usage: equity-table <output path> [trials per value]");
+    let trials = args.get(1)
+        .map(|t| t.parse().expect("Trials must be a positive number"))
+        .unwrap_or(10_000);
+    let table = equity_table::generate(config::DEFAULT_WORKER_COUNT, trials);
+    let mut writer = BufWriter::new(File::create(path).expect("Failed to create the output file"));
+    table.save(&mut writer).expect("Failed to write the equity table");
+}
+
+const PUSH_FOLD_USAGE: &str = "Usage: push-fold <equity table path> [--stack BB] [--ante BB] [--players N] [--iterations N]";
+
+/// Prints push charts of each position and call charts against each of them.
+fn print_push_fold_charts(args: &[String]) {
+    let path = args.first().unwrap_or_else(|| exit_with_push_fold_usage("Equity table is missing"));
+    let mut config = PushFoldConfig { stack: 10.0, ante: 0.0, num_of_players: 2 };
+    let mut iterations = 1000;
+    let mut iter = args[1 ..].iter();
+    while let Some(option) = iter.next() {
+        let value = iter.next().unwrap_or_else(|| exit_with_push_fold_usage(&format!("Value of {} is missing", option)));
+        match option.as_str() {
+            "--stack" => config.stack = parse_push_fold_option(option, value),
+            "--ante" => config.ante = parse_push_fold_option(option, value),
+            "--players" => config.num_of_players = parse_push_fold_option(option, value),
+            "--iterations" => iterations = parse_push_fold_option(option, value),
+            _ => exit_with_push_fold_usage(&format!("Unknown option: {}", option)),
+        }
+    }
+    if !(2 ..= 10).contains(&config.num_of_players) {
+        exit_with_push_fold_usage("The number of players must be 2 to 10");
+    }
+    if config.ante < 0.0 || config.stack <= 1.0 + config.ante {
+        exit_with_push_fold_usage("The stack must be larger than the big blind and the ante");
+    }
+
+    let file = File::open(path).expect("Failed to open the equity table");
+    let table = PreFlopEquityTable::load(io::BufReader::new(file)).expect("Failed to read the equity table");
+    let chart = PushFoldChart::solve(&config, &table, iterations);
+    let colored = io::stdout().is_terminal();
+    println!(
+        "{} players, {}bb stack, {}bb ante, max regret {:.4}bb after {} iterations",
+        config.num_of_players, config.stack, config.ante, chart.max_regret(), iterations
+    );
+    for pusher in chart.pushers() {
+        println!("\n{} push", pusher.name());
+        print!("{}", HandGrid::from_fn(|c| Some(chart.push_frequency(*pusher, c))).render_terminal(colored));
+        for caller in chart.callers(*pusher) {
+            println!("\n{} call against {} push", caller.name(), pusher.name());
+            print!("{}", HandGrid::from_fn(|c| Some(chart.call_frequency(*pusher, *caller, c))).render_terminal(colored));
+        }
     }
+}
+
+fn parse_push_fold_option<T: std::str::FromStr>(option: &str, value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with_push_fold_usage(&format!("Invalid value of {}: {}", option, value)))
+}
+
+fn exit_with_push_fold_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", PUSH_FOLD_USAGE);
+    process::exit(2);
+}
 
-    let results: Vec<&AggregationResult> = aggregations.values().collect();
+fn run_simulation(args: &[String]) {
+    let config = SimulationConfig::from_args(args).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        eprintln!("{}", config::USAGE);
+        process::exit(2);
+    });
 
-    println!("{}", AggregationResult::csv_head());
-    for r in results {
-        println!("{}", r.csv_row());
+    let stop = Arc::new(AtomicBool::new(false));
+    let handler_stop = stop.clone();
+    ctrlc::set_handler(move || {
+        if handler_stop.swap(true, Ordering::SeqCst) {
+            // interrupted twice
+            process::exit(130);
+        }
+        eprintln!("Interrupted. Writing partial results...");
+    }).expect("Failed to set the Ctrl-C handler");
+
+    let state = simulation::run(&config, stop);
+    let results = if config.by_position {
+        state.table.occurred_position_results()
+    } else {
+        state.table.occurred_results()
+    };
+
+    let metadata = Metadata {
+        seed: state.seed,
+        num_of_players: state.num_of_players,
+        strategy: String::from(street::strategy_by_name(&config.strategy).expect("strategy is validated on parsing").name()),
+        bets: config.bets.spec(),
+        trial_count: state.completed_trials(),
+    };
+    let mut out: Box<dyn Write> = match &config.output_path {
+        Some(path) => Box::new(BufWriter::new(File::create(path).expect("Failed to create the output file"))),
+        None => Box::new(io::stdout()),
+    };
+    config.format.writer()
+        .write(&mut out, &metadata, &results)
+        .and_then(|_| out.flush())
+        .expect("Failed to write the results");
+
+    let metric = grid::metric_by_name(&config.grid_metric).expect("metric is validated on parsing");
+    let grid_results = match config.grid_position {
+        Some(position) => state.table.occurred_position_results().into_iter().filter(|r| r.position() == Some(position)).collect(),
+        None => state.table.occurred_results(),
+    };
+    let hand_grid = HandGrid::from_results(&grid_results, metric);
+    if config.show_grid {
+        eprint!("{}", hand_grid.render_terminal(io::stderr().is_terminal()));
+    }
+    if let Some(path) = &config.html_path {
+        let position = config.grid_position.map_or("all positions", |p| p.name());
+        let title = format!("{} at {} ({} players, {} trials)", config.grid_metric, position, state.num_of_players, state.completed_trials());
+        std::fs::write(path, hand_grid.render_html(&title)).expect("Failed to write the HTML file");
     }
 }
@@ -0,0 +1,258 @@
+use crate::aggregator::AggregationResult;
+use crate::worker::TrialResult;
+use std::io::{ self, Write };
+
+/// Settings of a simulation run written along with the results.
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    pub seed: u64,
+    pub num_of_players: usize,
+    pub strategy: String,
+    /// Bet sizes in the format of BetModel::spec().
+    pub bets: String,
+    pub trial_count: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+    JsonLines,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "jsonl" => Some(Self::JsonLines),
+            _ => None,
+        }
+    }
+
+    pub fn writer(self) -> Box<dyn ResultWriter> {
+        match self {
+            Self::Csv => Box::new(CsvWriter),
+            Self::Json => Box::new(JsonWriter),
+            Self::JsonLines => Box::new(JsonLinesWriter),
+        }
+    }
+}
+
+pub trait ResultWriter {
+    /// Writes results in order of the given slice.
+    fn write(&self, out: &mut dyn Write, metadata: &Metadata, results: &[&AggregationResult]) -> io::Result<()>;
+}
+
+const RATE_COLUMNS: [&str; 5] = [
+    "win_rate",
+    "win_rate_std_error",
+    "show_down_win_rate",
+    "ev_per_hand",
+    "bb_per_100",
+];
+
+/// Hand, position, the number of occurrences, counts of every TrialResult and derived rates.
+fn columns() -> Vec<&'static str> {
+    let mut columns = vec!["hand", "position", "number_of_occurrence"];
+    columns.extend(TrialResult::all().iter().map(|r| r.name()));
+    columns.extend(RATE_COLUMNS.iter());
+    columns
+}
+
+/// Values of a result row in order of columns().
+fn values(result: &AggregationResult) -> Vec<Value> {
+    let mut values = vec![
+        Value::Text(result.hand_summary()),
+        // "all" for results of all positions
+        Value::Text(String::from(result.position().map_or("all", |p| p.name()))),
+        Value::Count(result.total_count() as u64),
+    ];
+    values.extend(TrialResult::all().iter().map(|r| Value::Count(result.count_of(*r) as u64)));
+    values.extend(vec![
+        Value::Rate(result.win_rate()),
+        Value::Rate(result.win_rate_std_error()),
+        Value::Rate(result.show_down_win_rate()),
+        Value::Rate(result.ev_per_hand()),
+        Value::Rate(result.bb_per_100()),
+    ]);
+    values
+}
+
+fn fields(result: &AggregationResult) -> Vec<(&'static str, Value)> {
+    columns().into_iter().zip(values(result)).collect()
+}
+
+fn metadata_fields(metadata: &Metadata) -> Vec<(&'static str, Value)> {
+    vec![
+        ("seed", Value::Count(metadata.seed)),
+        ("num_of_players", Value::Count(metadata.num_of_players as u64)),
+        ("strategy", Value::Text(metadata.strategy.clone())),
+        ("bets", Value::Text(metadata.bets.clone())),
+        ("trial_count", Value::Count(metadata.trial_count as u64)),
+    ]
+}
+
+enum Value {
+    Text(String),
+    Count(u64),
+    Rate(f64),
+}
+
+impl Value {
+    fn to_plain(&self) -> String {
+        match self {
+            Self::Text(s) => s.clone(),
+            Self::Count(n) => n.to_string(),
+            Self::Rate(r) => format!("{:.6}", r),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            Self::Text(s) => json_string(s),
+            _ => self.to_plain(),
+        }
+    }
+}
+
+/// Quotes a CSV field if it contains a comma, a quote or a line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_object(fields: &[(&'static str, Value)]) -> String {
+    let members: Vec<String> = fields.iter()
+        .map(|(k, v)| format!("{}:{}", json_string(k), v.to_json()))
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// CSV with the metadata in comment lines starting with '#'.
+pub struct CsvWriter;
+
+impl ResultWriter for CsvWriter {
+    fn write(&self, out: &mut dyn Write, metadata: &Metadata, results: &[&AggregationResult]) -> io::Result<()> {
+        for (key, value) in metadata_fields(metadata) {
+            writeln!(out, "# {}: {}", key, value.to_plain())?;
+        }
+        let head: Vec<String> = columns().iter().map(|c| csv_field(c)).collect();
+        writeln!(out, "{}", head.join(","))?;
+        for r in results {
+            let row: Vec<String> = values(r).iter().map(|v| csv_field(&v.to_plain())).collect();
+            writeln!(out, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// A JSON object with "metadata" and "results" members.
+pub struct JsonWriter;
+
+impl ResultWriter for JsonWriter {
+    fn write(&self, out: &mut dyn Write, metadata: &Metadata, results: &[&AggregationResult]) -> io::Result<()> {
+        let rows: Vec<String> = results.iter().map(|r| json_object(&fields(r))).collect();
+        writeln!(out, "{{\"metadata\":{},\"results\":[{}]}}", json_object(&metadata_fields(metadata)), rows.join(","))
+    }
+}
+
+/// A line of the metadata object followed by a line per result.
+pub struct JsonLinesWriter;
+
+impl ResultWriter for JsonLinesWriter {
+    fn write(&self, out: &mut dyn Write, metadata: &Metadata, results: &[&AggregationResult]) -> io::Result<()> {
+        writeln!(out, "{{\"metadata\":{}}}", json_object(&metadata_fields(metadata)))?;
+        for r in results {
+            writeln!(out, "{}", json_object(&fields(r)))?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+    use texas_holdem::card::StartingHand;
+
+    fn result(a: (Suit, u8), b: (Suit, u8)) -> AggregationResult {
+        let hand = StartingHand::new(
+            NonJokerCard::new(a.0, CardRank::new(a.1)),
+            NonJokerCard::new(b.0, CardRank::new(b.1)),
+        );
+        let mut result = AggregationResult::new(hand.class());
+        result.count_up(TrialResult::ShowDownWin);
+        result.count_up(TrialResult::FlopDrop);
+        result.add_net_chips(3.0);
+        result
+    }
+
+    fn metadata() -> Metadata {
+        Metadata { seed: 42, num_of_players: 6, strategy: String::from("a, \"b\""), bets: String::from("2,2,4,4"), trial_count: 100 }
+    }
+
+    fn write(format: OutputFormat, results: &[&AggregationResult]) -> String {
+        let mut buffer = Vec::new();
+        format.writer().write(&mut buffer, &metadata(), results).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn csv_has_metadata_head_and_rows() {
+        let aces = result((Suit::Spade, 1), (Suit::Heart, 1));
+        let text = write(OutputFormat::Csv, &[&aces]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!("# seed: 42", lines[0]);
+        assert_eq!("# bets: 2,2,4,4", lines[3]);
+        assert!(lines[5].starts_with("hand,position,number_of_occurrence,"));
+        assert_eq!("AA,all,2,0,0,0,1,0,0,0,0,1,0,0,0.500000,0.353553,1.000000,0.500000,150.000000", lines[6]);
+    }
+
+    #[test]
+    fn csv_field_is_quoted_when_needed() {
+        assert_eq!("abc", csv_field("abc"));
+        assert_eq!("\"a,b\"", csv_field("a,b"));
+        assert_eq!("\"a\"\"b\"", csv_field("a\"b"));
+    }
+
+    #[test]
+    fn json_has_metadata_and_results() {
+        let aces = result((Suit::Spade, 1), (Suit::Heart, 1));
+        let text = write(OutputFormat::Json, &[&aces]);
+        assert!(text.starts_with("{\"metadata\":{\"seed\":42,\"num_of_players\":6,\"strategy\":\"a, \\\"b\\\"\",\"bets\":\"2,2,4,4\",\"trial_count\":100},"));
+        assert!(text.contains("\"results\":[{\"hand\":\"AA\",\"position\":\"all\",\"number_of_occurrence\":2,"));
+    }
+
+    #[test]
+    fn json_lines_has_a_line_per_result() {
+        let aces = result((Suit::Spade, 1), (Suit::Heart, 1));
+        let kings = result((Suit::Spade, 13), (Suit::Heart, 13));
+        let text = write(OutputFormat::JsonLines, &[&aces, &kings]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(3, lines.len());
+        assert!(lines[0].starts_with("{\"metadata\":"));
+        assert!(lines[2].starts_with("{\"hand\":\"KK\","));
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::{ Duration, Instant };
+
+/// Counts completed trials and reports the rate and the estimated remaining time.
+pub struct Progress {
+    total: usize,
+    completed: usize,
+    /// Trials completed before this run, e.g. ones restored from a checkpoint.
+    initial: usize,
+    started: Instant,
+    last_report: Instant,
+    report_interval: Duration,
+}
+
+impl Progress {
+    pub fn new(total: usize, initial: usize, report_interval: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            total,
+            completed: initial,
+            initial,
+            started: now,
+            last_report: now,
+            report_interval,
+        }
+    }
+
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    pub fn add(&mut self, trials: usize) {
+        self.completed += trials;
+    }
+
+    /// Trials per second in this run.
+    pub fn rate(&self) -> f64 {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed == 0.0 { 0.0 } else { (self.completed - self.initial) as f64 / elapsed }
+    }
+
+    /// Estimated time until all trials complete. None until the rate is known.
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.rate();
+        if rate == 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.completed);
+        Some(Duration::from_secs_f64(remaining as f64 / rate))
+    }
+
+    /// Returns a report line if the report interval has passed since the last report.
+    pub fn report_if_due(&mut self) -> Option<String> {
+        if self.last_report.elapsed() < self.report_interval {
+            return None;
+        }
+        self.last_report = Instant::now();
+        Some(self.report())
+    }
+
+    pub fn report(&self) -> String {
+        let eta = match self.eta() {
+            Some(d) => format_duration(d),
+            None => String::from("-"),
+        };
+        format!(
+            "{} / {} trials ({:.1}%), {:.0} trials/s, ETA {}",
+            self.completed,
+            self.total,
+            100.0 * self.completed as f64 / self.total.max(1) as f64,
+            self.rate(),
+            eta,
+        )
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m{:02}s", secs / 3600, secs % 3600 / 60, secs % 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn completed_includes_initial_trials() {
+        let mut progress = Progress::new(100, 30, Duration::from_secs(1));
+        progress.add(5);
+        assert_eq!(35, progress.completed());
+    }
+
+    #[test]
+    fn report_is_not_due_before_interval() {
+        let mut progress = Progress::new(100, 0, Duration::from_secs(3600));
+        assert_eq!(None, progress.report_if_due());
+    }
+
+    #[test]
+    fn report_shows_counts() {
+        let mut progress = Progress::new(200, 0, Duration::from_secs(0));
+        progress.add(50);
+        assert!(progress.report_if_due().unwrap().starts_with("50 / 200 trials (25.0%), "));
+    }
+
+    #[test]
+    fn duration_is_formatted() {
+        assert_eq!("42s", format_duration(Duration::from_secs(42)));
+        assert_eq!("3m05s", format_duration(Duration::from_secs(185)));
+        assert_eq!("2h00m01s", format_duration(Duration::from_secs(7201)));
+    }
+}
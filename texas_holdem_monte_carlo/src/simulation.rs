@@ -0,0 +1,159 @@
+use crate::checkpoint::SimulationState;
+use crate::config::SimulationConfig;
+use crate::progress::Progress;
+use crate::street;
+use crate::worker;
+use rand::Rng;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{ Duration, Instant };
+use texas_holdem::parallel::Executor;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of trials a worker runs with one seed at a time.
+const TRIALS_PER_PART: usize = 1024;
+
+/// Runs trials with workers until all trials complete or `stop` is set,
+/// and returns the aggregated results. Results of the parts are merged in order of their trials.
+pub fn run(config: &SimulationConfig, stop: Arc<AtomicBool>) -> SimulationState {
+    let mut state = match &config.resume_path {
+        Some(path) => {
+            let state = SimulationState::load(path).expect("Failed to load the checkpoint");
+            state.check_settings(&config.strategy, &config.bets).expect("Failed to resume the simulation");
+            eprintln!(
+                "Resuming from {} trials (seed {}, {} players)",
+                state.completed_trials(), state.seed, state.num_of_players
+            );
+            state
+        },
+        None => {
+            let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+            SimulationState::new(seed, config.num_of_players, &config.strategy, config.bets.clone())
+        },
+    };
+    run_unfinished_parts(&mut state, config, &stop);
+    if let Some(path) = &config.checkpoint_path {
+        state.save(path).expect("Failed to save the checkpoint");
+    }
+    state
+}
+
+/// Runs the parts of trials which are not finished in the state.
+/// A part interrupted by `stop` is not merged, so that a resumed run can run it again from its first trial.
+fn run_unfinished_parts(state: &mut SimulationState, config: &SimulationConfig, stop: &AtomicBool) {
+    let total_trials = config.trial_count();
+    let strategy = street::strategy_by_name(&config.strategy).expect("strategy is validated on parsing");
+    let (seed, num_of_players) = (state.seed, state.num_of_players);
+    let parts = unfinished_parts(state, total_trials);
+
+    let mut progress = Progress::new(total_trials, state.completed_trials(), PROGRESS_INTERVAL);
+    let checkpoint_interval = Duration::from_secs(config.checkpoint_interval_secs);
+    let mut last_checkpoint = Instant::now();
+    Executor::new(config.worker_count).for_each_ordered(
+        parts,
+        |(index, trial_count)| {
+            let delta = worker::run_trials(num_of_players, trial_count, part_seed(seed, index), strategy.as_ref(), &config.bets, stop);
+            (index, trial_count, delta)
+        },
+        |(index, trial_count, delta)| {
+            if delta.trial_count() < trial_count {
+                return;
+            }
+            progress.add(trial_count);
+            state.finish_part(index, &delta);
+            if let Some(report) = progress.report_if_due() {
+                eprintln!("{}", report);
+            }
+            if let Some(path) = &config.checkpoint_path {
+                if last_checkpoint.elapsed() >= checkpoint_interval {
+                    state.save(path).expect("Failed to save the checkpoint");
+                    last_checkpoint = Instant::now();
+                }
+            }
+        },
+    );
+    eprintln!("{}", progress.report());
+}
+
+/// Indexes and trial counts of the parts which are not finished in the state.
+/// Part i has the trials from i * TRIALS_PER_PART.
+fn unfinished_parts(state: &SimulationState, total_trials: usize) -> Vec<(usize, usize)> {
+    (0 .. total_trials.div_ceil(TRIALS_PER_PART))
+        .filter(|i| !state.finished_parts.contains(i))
+        .map(|i| (i, TRIALS_PER_PART.min(total_trials - i * TRIALS_PER_PART)))
+        .collect()
+}
+
+/// Seed of the part with given index, which does not depend on the parts finished before,
+/// so that a resumed run does not replay the dealt cards of the finished parts.
+fn part_seed(seed: u64, index: usize) -> u64 {
+    seed.wrapping_add(index as u64)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aggregator::AggregationTable;
+    use crate::worker::TrialResult;
+
+    fn config() -> SimulationConfig {
+        SimulationConfig { worker_count: 2, trial_per_worker: 2 * TRIALS_PER_PART, seed: Some(7), ..SimulationConfig::default() }
+    }
+
+    fn new_state(config: &SimulationConfig) -> SimulationState {
+        SimulationState::new(config.seed.unwrap(), config.num_of_players, &config.strategy, config.bets.clone())
+    }
+
+    fn run_part(state: &SimulationState, config: &SimulationConfig, index: usize) -> AggregationTable {
+        let strategy = street::strategy_by_name(&config.strategy).unwrap();
+        let seed = part_seed(state.seed, index);
+        worker::run_trials(state.num_of_players, TRIALS_PER_PART, seed, strategy.as_ref(), &config.bets, &AtomicBool::new(false))
+    }
+
+    #[test]
+    fn resume_runs_only_unfinished_parts() {
+        let config = config();
+        let mut state = new_state(&config);
+        state.finish_part(2, &run_part(&state, &config, 2));
+        let parts: Vec<usize> = unfinished_parts(&state, config.trial_count()).iter().map(|(i, _)| *i).collect();
+        assert_eq!(vec![0, 1, 3], parts);
+    }
+
+    #[test]
+    fn resume_after_parts_finished_out_of_order_gives_the_same_results() {
+        let config = config();
+        let mut uninterrupted = new_state(&config);
+        run_unfinished_parts(&mut uninterrupted, &config, &AtomicBool::new(false));
+
+        // parts 2 and 0 finished before an interruption, while part 1 did not
+        let mut resumed = new_state(&config);
+        for index in [2, 0].iter() {
+            let delta = run_part(&resumed, &config, *index);
+            resumed.finish_part(*index, &delta);
+        }
+        run_unfinished_parts(&mut resumed, &config, &AtomicBool::new(false));
+
+        assert_eq!(config.trial_count(), resumed.completed_trials());
+        assert_eq!(uninterrupted.finished_parts, resumed.finished_parts);
+        let (expected_results, actual_results) = (uninterrupted.table.occurred_position_results(), resumed.table.occurred_position_results());
+        assert_eq!(expected_results.len(), actual_results.len());
+        for (expected, actual) in expected_results.iter().zip(actual_results) {
+            assert_eq!((expected.hand_class(), expected.position()), (actual.hand_class(), actual.position()));
+            for t in TrialResult::all().iter() {
+                assert_eq!(expected.count_of(*t), actual.count_of(*t));
+            }
+            assert!((expected.net_chips() - actual.net_chips()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn interrupted_part_is_not_merged() {
+        let config = config();
+        let mut state = new_state(&config);
+        run_unfinished_parts(&mut state, &config, &AtomicBool::new(true));
+        assert_eq!(0, state.completed_trials());
+        assert!(state.finished_parts.is_empty());
+    }
+}
@@ -30,6 +30,6 @@ fn evaluate_card_rank(rank: CardRank) -> u8 {
         10 ..= 12 => 2,
         6 ..= 9 => 1,
         2 ..= 5 => 0,
-        _ => panic!(format!("Unexpected card: {}", rank)),
+        _ => panic!("Unexpected card: {}", rank),
     }
 }
@@ -0,0 +1,373 @@
+use crate::flop_hand;
+use crate::starting_hand;
+use crate::worker::TrialResult;
+use texas_holdem::Phase;
+use texas_holdem::card::{ Board, HandValue, StartingHand, TotalHand };
+use texas_holdem::position::Position;
+
+/// Decides whether a player continues to the next street.
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Returns true if the player with the hand in the position continues on the current phase of the board.
+    fn continues(&self, hand: StartingHand, position: Position, board: &Board) -> bool;
+}
+
+/// Plays by the starting hand value before flop and the flop hand evaluation on flop,
+/// and never drops on turn and river.
+pub struct DefaultStrategy;
+
+impl Strategy for DefaultStrategy {
+    fn name(&self) -> &'static str {
+        "starting_hand_value+flop_hand"
+    }
+
+    fn continues(&self, hand: StartingHand, _position: Position, board: &Board) -> bool {
+        match board.current_phase() {
+            Phase::PreFlop => starting_hand::evaluate_hand(hand),
+            Phase::Flop => flop_hand::evaluate_hand(hand, board),
+            Phase::Turn | Phase::River => true,
+        }
+    }
+}
+
+/// Never drops, so every hand goes to showdown.
+pub struct CallingStation;
+
+impl Strategy for CallingStation {
+    fn name(&self) -> &'static str {
+        "calling_station"
+    }
+
+    fn continues(&self, _hand: StartingHand, _position: Position, _board: &Board) -> bool {
+        true
+    }
+}
+
+pub fn strategy_by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "default" => Some(Box::new(DefaultStrategy)),
+        "calling_station" => Some(Box::new(CallingStation)),
+        _ => None,
+    }
+}
+
+/// Big blinds posted by the small blind. All chip amounts are in big blinds.
+pub const SMALL_BLIND: f64 = 0.5;
+pub const BIG_BLIND: f64 = 1.0;
+
+/// Bet sizes of a simplified betting round, where every player who continues on a street
+/// puts in the same amount and no one re-raises.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BetModel {
+    /// Total amount each continuing player has put in before flop, including the blind.
+    pub pre_flop: f64,
+    /// Amounts each continuing player puts in on flop, turn and river.
+    pub post_flop: [f64; 3],
+}
+
+impl Default for BetModel {
+    /// Fixed limit with a raise before flop: 2 big blinds before flop and on flop, 4 on turn and river.
+    fn default() -> Self {
+        Self { pre_flop: 2.0, post_flop: [2.0, 4.0, 4.0] }
+    }
+}
+
+impl BetModel {
+    /// Parses bet sizes of four streets separated by commas, e.g. "2,2,4,4".
+    /// The pre-flop bet must be at least the big blind.
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        let bets: Vec<f64> = spec.split(',').map(|b| b.trim().parse().ok()).collect::<Option<_>>()?;
+        if bets.len() != 4 || bets[0] < BIG_BLIND || bets.iter().any(|b| !b.is_finite() || *b < 0.0) {
+            return None;
+        }
+        Some(Self { pre_flop: bets[0], post_flop: [bets[1], bets[2], bets[3]] })
+    }
+
+    /// Bet sizes in the format of from_spec().
+    pub fn spec(&self) -> String {
+        format!("{},{},{},{}", self.pre_flop, self.post_flop[0], self.post_flop[1], self.post_flop[2])
+    }
+
+    pub fn blind_of(&self, position: Position) -> f64 {
+        match position {
+            Position::SmallBlind => SMALL_BLIND,
+            Position::BigBlind => BIG_BLIND,
+            _ => 0.0,
+        }
+    }
+
+    /// Total amount a player who continues on the phase has put in, given the amount before the phase.
+    fn invested_after(&self, phase: Phase, invested: f64) -> f64 {
+        match phase {
+            Phase::PreFlop => invested.max(self.pre_flop),
+            Phase::Flop => invested + self.post_flop[0],
+            Phase::Turn => invested + self.post_flop[1],
+            Phase::River => invested + self.post_flop[2],
+        }
+    }
+}
+
+/// Result of a player in a played hand.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Outcome {
+    pub hand: StartingHand,
+    pub position: Position,
+    pub result: TrialResult,
+    /// Chips won minus chips put in, in big blinds.
+    pub net: f64,
+}
+
+struct Player {
+    hand: StartingHand,
+    position: Position,
+    invested: f64,
+}
+
+/// Plays a hand street by street from the current phase of the board to showdown,
+/// and records the outcome of every player.
+///
+/// The blinds are posted first. On each street, players who do not continue drop and lose what they put in.
+/// If only one player continues, the player takes the pot without a call and without dealing further cards.
+/// If no one continues before flop, the big blind takes the blinds as a walk.
+/// If no one continues after flop, no one bets and all players stay.
+pub fn play_hand<F: FnMut(Outcome)>(
+    seats: Vec<(StartingHand, Position)>,
+    board: &mut Board,
+    strategy: &dyn Strategy,
+    bets: &BetModel,
+    mut record: F,
+) {
+    let mut players: Vec<Player> = seats.into_iter()
+        .map(|(hand, position)| Player { hand, position, invested: bets.blind_of(position) })
+        .collect();
+    let mut pot: f64 = players.iter().map(|p| p.invested).sum();
+    loop {
+        let phase = board.current_phase();
+        let (mut continuing, mut dropping): (Vec<Player>, Vec<Player>) = players.into_iter()
+            .partition(|p| strategy.continues(p.hand, p.position, board));
+        if continuing.is_empty() && phase == Phase::PreFlop {
+            // the player who posted the most, i.e. the big blind, is not asked to call
+            let walk = (0 .. dropping.len())
+                .max_by(|a, b| dropping[*a].invested.total_cmp(&dropping[*b].invested))
+                .expect("No players are seated");
+            continuing.push(dropping.remove(walk));
+        }
+        if continuing.is_empty() {
+            players = dropping;
+        } else {
+            for p in dropping {
+                record(Outcome { hand: p.hand, position: p.position, result: TrialResult::drop_on(phase), net: -p.invested });
+            }
+            players = continuing;
+            if players.len() == 1 {
+                let p = &players[0];
+                record(Outcome { hand: p.hand, position: p.position, result: TrialResult::win_on(phase), net: pot - p.invested });
+                return;
+            }
+            for p in players.iter_mut() {
+                let invested = bets.invested_after(phase, p.invested);
+                pot += invested - p.invested;
+                p.invested = invested;
+            }
+        }
+        if phase == Phase::River {
+            break;
+        }
+        board.deal_next_card();
+    }
+    show_down(&players, pot, board, record);
+}
+
+fn show_down<F: FnMut(Outcome)>(players: &[Player], pot: f64, board: &Board, mut record: F) {
+    let values: Vec<HandValue> = players.iter()
+        .map(|p| {
+            TotalHand::new_from_starting_hand_and_board(p.hand, board)
+                .find_best_five_hand()
+                .expect("best five hand not found with 7 cards")
+                .value()
+        })
+        .collect();
+    let best = *values.iter().max().expect("No hands reached showdown");
+    let winner_count = values.iter().filter(|v| **v == best).count();
+    for (p, v) in players.iter().zip(values.iter()) {
+        let (result, won) = if *v != best {
+            (TrialResult::ShowDownLose, 0.0)
+        } else if winner_count == 1 {
+            (TrialResult::ShowDownWin, pot)
+        } else {
+            (TrialResult::ShowDownSplit, pot / winner_count as f64)
+        };
+        record(Outcome { hand: p.hand, position: p.position, result, net: won - p.invested });
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    /// Continues only with hands in the list on given phase, and always on other phases.
+    struct Scripted {
+        phase: Phase,
+        hands: Vec<StartingHand>,
+    }
+
+    impl Strategy for Scripted {
+        fn name(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn continues(&self, hand: StartingHand, _position: Position, board: &Board) -> bool {
+            board.current_phase() != self.phase || self.hands.contains(&hand)
+        }
+    }
+
+    /// Hands of UTG, SB and BB in this order.
+    fn hands() -> Vec<StartingHand> {
+        vec![
+            StartingHand::new(card(Suit::Spade, 1), card(Suit::Heart, 1)),
+            StartingHand::new(card(Suit::Spade, 13), card(Suit::Heart, 13)),
+            StartingHand::new(card(Suit::Spade, 7), card(Suit::Heart, 2)),
+        ]
+    }
+
+    fn seats(hands: Vec<StartingHand>) -> Vec<(StartingHand, Position)> {
+        let positions = [Position::UnderTheGun, Position::SmallBlind, Position::BigBlind];
+        hands.into_iter().zip(positions.iter().copied()).collect()
+    }
+
+    fn play(strategy: &dyn Strategy) -> (Vec<Outcome>, Board) {
+        let hands = hands();
+        let mut board = Board::new_without_cards(&hands.iter().flat_map(|h| h.both_cards().to_vec()).collect::<Vec<_>>());
+        let mut outcomes = Vec::new();
+        play_hand(seats(hands), &mut board, strategy, &BetModel::default(), |o| outcomes.push(o));
+        (outcomes, board)
+    }
+
+    fn outcome_of(outcomes: &[Outcome], hand: StartingHand) -> Outcome {
+        *outcomes.iter().find(|o| o.hand == hand).unwrap()
+    }
+
+    fn net_total(outcomes: &[Outcome]) -> f64 {
+        outcomes.iter().map(|o| o.net).sum()
+    }
+
+    #[test]
+    fn single_player_wins_pre_flop_without_dealing() {
+        let (outcomes, board) = play(&Scripted { phase: Phase::PreFlop, hands: vec![hands()[0]] });
+        assert_eq!(Phase::PreFlop, board.current_phase());
+        assert_eq!(TrialResult::PreFlopWin, outcomes[2].result);
+        assert_eq!(hands()[0], outcomes[2].hand);
+        assert_eq!(TrialResult::PreFlopDrop, outcomes[0].result);
+        assert_eq!(TrialResult::PreFlopDrop, outcomes[1].result);
+    }
+
+    #[test]
+    fn pre_flop_winner_takes_the_blinds() {
+        let (outcomes, _) = play(&Scripted { phase: Phase::PreFlop, hands: vec![hands()[0]] });
+        assert_eq!(1.5, outcome_of(&outcomes, hands()[0]).net);
+        assert_eq!(-0.5, outcome_of(&outcomes, hands()[1]).net);
+        assert_eq!(-1.0, outcome_of(&outcomes, hands()[2]).net);
+    }
+
+    #[test]
+    fn big_blind_wins_small_blind_when_others_drop() {
+        let (outcomes, _) = play(&Scripted { phase: Phase::PreFlop, hands: vec![hands()[2]] });
+        assert_eq!(0.5, outcome_of(&outcomes, hands()[2]).net);
+        assert_eq!(0.0, outcome_of(&outcomes, hands()[0]).net);
+    }
+
+    #[test]
+    fn single_player_wins_on_flop_without_dealing_turn() {
+        let (outcomes, board) = play(&Scripted { phase: Phase::Flop, hands: vec![hands()[1]] });
+        assert_eq!(Phase::Flop, board.current_phase());
+        let winner = outcome_of(&outcomes, hands()[1]);
+        assert_eq!(TrialResult::FlopWin, winner.result);
+        // everyone put in 2 big blinds before flop
+        assert_eq!(4.0, winner.net);
+        assert_eq!(2, outcomes.iter().filter(|o| o.result == TrialResult::FlopDrop && o.net == -2.0).count());
+    }
+
+    #[test]
+    fn single_player_wins_on_turn_without_dealing_river() {
+        let (outcomes, board) = play(&Scripted { phase: Phase::Turn, hands: vec![hands()[2]] });
+        assert_eq!(Phase::Turn, board.current_phase());
+        assert_eq!(TrialResult::TurnWin, outcome_of(&outcomes, hands()[2]).result);
+        assert_eq!(8.0, outcome_of(&outcomes, hands()[2]).net);
+    }
+
+    #[test]
+    fn single_player_wins_on_river_without_showdown() {
+        let (outcomes, board) = play(&Scripted { phase: Phase::River, hands: vec![hands()[0]] });
+        assert_eq!(Phase::River, board.current_phase());
+        assert_eq!(TrialResult::RiverWin, outcome_of(&outcomes, hands()[0]).result);
+        assert_eq!(2, outcomes.iter().filter(|o| o.result == TrialResult::RiverDrop).count());
+        assert_eq!(0.0, net_total(&outcomes));
+    }
+
+    #[test]
+    fn showdown_is_on_river() {
+        let (outcomes, board) = play(&CallingStation);
+        assert_eq!(5, board.cards().len());
+        assert_eq!(3, outcomes.len());
+        assert!(outcomes.iter().all(|o| o.result.phase() == Phase::River));
+        assert_eq!(0.0, net_total(&outcomes));
+    }
+
+    #[test]
+    fn big_blind_takes_a_walk_when_no_one_continues_pre_flop() {
+        let (outcomes, board) = play(&Scripted { phase: Phase::PreFlop, hands: Vec::new() });
+        assert_eq!(Phase::PreFlop, board.current_phase());
+        let big_blind = outcome_of(&outcomes, hands()[2]);
+        assert_eq!(TrialResult::PreFlopWin, big_blind.result);
+        assert_eq!(0.5, big_blind.net);
+        assert_eq!(-0.5, outcome_of(&outcomes, hands()[1]).net);
+        assert_eq!(TrialResult::PreFlopDrop, outcome_of(&outcomes, hands()[0]).result);
+        assert_eq!(0.0, outcome_of(&outcomes, hands()[0]).net);
+    }
+
+    #[test]
+    fn all_players_stay_when_no_one_continues_after_flop() {
+        let (outcomes, board) = play(&Scripted { phase: Phase::Flop, hands: Vec::new() });
+        assert_eq!(Phase::River, board.current_phase());
+        assert!(outcomes.iter().all(|o| o.result.phase() == Phase::River));
+        assert_eq!(0.0, net_total(&outcomes));
+    }
+
+    #[test]
+    fn tied_hands_split_pot() {
+        let hands = [
+            StartingHand::new(card(Suit::Spade, 2), card(Suit::Heart, 3)),
+            StartingHand::new(card(Suit::Club, 2), card(Suit::Diamond, 3)),
+        ];
+        let mut board = Board::new_with_cards(&[
+            card(Suit::Club, 10), card(Suit::Diamond, 11), card(Suit::Spade, 12), card(Suit::Heart, 13), card(Suit::Club, 1),
+        ]);
+        let seats = vec![(hands[0], Position::CutOff), (hands[1], Position::Button)];
+        let mut outcomes = Vec::new();
+        play_hand(seats, &mut board, &CallingStation, &BetModel::default(), |o| outcomes.push(o));
+        assert!(outcomes.iter().all(|o| o.result == TrialResult::ShowDownSplit && o.net == 0.0));
+    }
+
+    #[test]
+    fn bet_model_is_parsed_from_spec() {
+        let bets = BetModel::from_spec("3, 1.5,4,8").unwrap();
+        assert_eq!(BetModel { pre_flop: 3.0, post_flop: [1.5, 4.0, 8.0] }, bets);
+        assert_eq!("3,1.5,4,8", bets.spec());
+        assert_eq!(None, BetModel::from_spec("2,2,4"));
+        assert_eq!(None, BetModel::from_spec("0.5,2,4,4"));
+        assert_eq!(None, BetModel::from_spec("2,x,4,4"));
+    }
+
+    #[test]
+    fn strategies_are_found_by_name() {
+        assert_eq!("calling_station", strategy_by_name("calling_station").unwrap().name());
+        assert!(strategy_by_name("foo").is_none());
+    }
+}
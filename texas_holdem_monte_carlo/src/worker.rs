@@ -1,101 +1,140 @@
-use crate::starting_hand;
-use crate::flop_hand;
-use std::sync::mpsc::Sender;
+use crate::aggregator::AggregationTable;
+use crate::street::{ self, BetModel, Strategy };
+use rand::{ Rng, SeedableRng };
+use rand::rngs::StdRng;
+use std::sync::atomic::{ AtomicBool, Ordering };
 use texas_holdem::Phase;
-use texas_holdem::card::{ BestFiveHand, Board, StartingHand, TotalHand };
+use texas_holdem::card::Board;
+use texas_holdem::position::Position;
 
 
-#[derive(Debug, Copy, Clone)]
-pub struct TrialResultOfHand {
-    starting_hand: StartingHand,
-    result: TrialResult,
-}
-
+/// Result of a starting hand in a trial. Each street has an outcome for the player who won
+/// because all others dropped, and one for the players who dropped.
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
 pub enum TrialResult {
     PreFlopWin,
-    FlopDrop,
+    PreFlopDrop,
     FlopWin,
+    FlopDrop,
+    TurnWin,
+    TurnDrop,
+    RiverWin,
+    RiverDrop,
     ShowDownWin,
+    ShowDownSplit,
     ShowDownLose,
 }
 
+impl TrialResult {
+    pub const COUNT: usize = 11;
 
-impl TrialResultOfHand {
-    pub fn starting_hand(self) -> StartingHand {
-        self.starting_hand
+    pub fn all() -> [Self; Self::COUNT] {
+        [
+            Self::PreFlopWin,
+            Self::PreFlopDrop,
+            Self::FlopWin,
+            Self::FlopDrop,
+            Self::TurnWin,
+            Self::TurnDrop,
+            Self::RiverWin,
+            Self::RiverDrop,
+            Self::ShowDownWin,
+            Self::ShowDownSplit,
+            Self::ShowDownLose,
+        ]
     }
 
-    pub fn result(self) -> TrialResult {
-        self.result
+    /// Position in all().
+    pub fn index(self) -> usize {
+        Self::all().iter().position(|r| *r == self).unwrap()
     }
-}
 
-pub fn run_worker(sender: Sender<TrialResultOfHand>, number_of_players: usize, trial_count: usize) {
-    for n in 1 ..= trial_count {
-        if n % 10000 == 0 {
-            eprintln!("{}", n);
+    pub fn win_on(phase: Phase) -> Self {
+        match phase {
+            Phase::PreFlop => Self::PreFlopWin,
+            Phase::Flop => Self::FlopWin,
+            Phase::Turn => Self::TurnWin,
+            Phase::River => Self::RiverWin,
         }
-        do_trial(&sender, number_of_players);
     }
-}
 
-fn do_trial(sender: &Sender<TrialResultOfHand>, num_of_players: usize) {
-    let mut board =  Board::new();
-    let mut hands = board.deal_starting_hands(num_of_players);
-    hands.retain(|h| starting_hand::evaluate_hand(*h));
-    if hands.len() <= 1 {
-        let winner = hands.get(0);
-        if let Some(&h) = winner {
-            let _ = sender.send(TrialResultOfHand { starting_hand: h, result: TrialResult::PreFlopWin });
+    pub fn drop_on(phase: Phase) -> Self {
+        match phase {
+            Phase::PreFlop => Self::PreFlopDrop,
+            Phase::Flop => Self::FlopDrop,
+            Phase::Turn => Self::TurnDrop,
+            Phase::River => Self::RiverDrop,
         }
     }
-    board.deal_cards_until(Phase::Flop);
-    let hands = flop_check(sender, hands, &board);
-    if hands.len() <= 1 {
-        let winner = hands.get(0);
-        if let Some(&h) = winner {
-            let _ = sender.send(TrialResultOfHand { starting_hand: h, result: TrialResult::FlopWin });
+
+    /// The phase on which the result was decided. Showdown is on the river.
+    pub fn phase(self) -> Phase {
+        match self {
+            Self::PreFlopWin | Self::PreFlopDrop => Phase::PreFlop,
+            Self::FlopWin | Self::FlopDrop => Phase::Flop,
+            Self::TurnWin | Self::TurnDrop => Phase::Turn,
+            _ => Phase::River,
         }
-        return;
     }
-    board.deal_cards_until(Phase::Flop);
-    show_down_check(&sender, hands, &board);
-}
 
-fn flop_check(sender: &Sender<TrialResultOfHand>, starting_hands: Vec<StartingHand>, board: &Board) -> Vec<StartingHand> {
-    let mut winners = Vec::new();
-    let mut losers = Vec::new();
-    for s in starting_hands.iter() {
-        if flop_hand::evaluate_hand(*s, board.flop().unwrap()) {
-            winners.push(*s);
-        } else {
-            losers.push(*s);
-        }
-    };
-    if winners.is_empty() {
-        starting_hands
-    } else {
-        for h in losers {
-            let _ = sender.send(TrialResultOfHand { starting_hand: h, result: TrialResult::FlopDrop });
-        }
-        winners
+    pub fn is_win(self) -> bool {
+        matches!(self, Self::PreFlopWin | Self::FlopWin | Self::TurnWin | Self::RiverWin | Self::ShowDownWin)
     }
-}
 
-fn show_down_check(sender: &Sender<TrialResultOfHand>, starting_hands: Vec<StartingHand>, board: &Board) {
-    let mut starts_and_bests: Vec<(StartingHand, BestFiveHand)> = starting_hands.iter().map(|starting| {
-        let total = TotalHand::new_from_starting_hand_and_board(*starting, board);
-        let best = total.find_best_five_hand().unwrap();
-        (*starting, best)
-    }).collect();
-    starts_and_bests.sort_by(|(_, a_best), (_, b_best)| a_best.value().cmp(&b_best.value()).reverse());
+    pub fn is_show_down(self) -> bool {
+        matches!(self, Self::ShowDownWin | Self::ShowDownSplit | Self::ShowDownLose)
+    }
 
-    let winner = starts_and_bests[0];
-    let _ = sender.send(TrialResultOfHand { starting_hand: winner.0, result: TrialResult::ShowDownWin });
+    /// Name used for output columns, e.g. "pre_flop_win".
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PreFlopWin => "pre_flop_win",
+            Self::PreFlopDrop => "pre_flop_drop",
+            Self::FlopWin => "flop_win",
+            Self::FlopDrop => "flop_drop",
+            Self::TurnWin => "turn_win",
+            Self::TurnDrop => "turn_drop",
+            Self::RiverWin => "river_win",
+            Self::RiverDrop => "river_drop",
+            Self::ShowDownWin => "show_down_win",
+            Self::ShowDownSplit => "show_down_split",
+            Self::ShowDownLose => "show_down_lose",
+        }
+    }
+}
 
-    for loser in starts_and_bests[1..].iter() {
-        let _ = sender.send(TrialResultOfHand { starting_hand: loser.0, result: TrialResult::ShowDownLose });
+/// Runs trials with cards shuffled by a random number generator seeded with given seed,
+/// and returns their aggregated results. Stops early when `stop` is set.
+pub fn run_trials(
+    number_of_players: usize,
+    trial_count: usize,
+    seed: u64,
+    strategy: &dyn Strategy,
+    bets: &BetModel,
+    stop: &AtomicBool,
+) -> AggregationTable {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut table = AggregationTable::new();
+    for _ in 0 .. trial_count {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        do_trial(&mut table, number_of_players, strategy, bets, &mut rng);
+        table.add_trials(1);
     }
+    table
 }
 
+fn do_trial<R: Rng>(
+    table: &mut AggregationTable,
+    num_of_players: usize,
+    strategy: &dyn Strategy,
+    bets: &BetModel,
+    rng: &mut R,
+) {
+    let mut board = Board::new_with_rng(rng);
+    let hands = board.deal_starting_hands(num_of_players);
+    // cards are dealt at random, so seat i can take the i-th position every time
+    let seats = hands.into_iter().zip(Position::of_table(num_of_players)).collect();
+    street::play_hand(seats, &mut board, strategy, bets, |o| table.record(&o));
+}
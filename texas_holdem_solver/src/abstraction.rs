@@ -0,0 +1,373 @@
+use rand::{ Rng, SeedableRng };
+use rand::rngs::StdRng;
+use std::io::{ self, BufRead, Write };
+use texas_holdem::Phase;
+use texas_holdem::card::{ Board, HandClass, StartingHand };
+use texas_holdem::equity::PreFlopEquityTable;
+use texas_holdem::hand_strength::HandStrength;
+
+const ABSTRACTION_VERSION: u32 = 1;
+
+const PHASES: [Phase; 4] = [Phase::PreFlop, Phase::Flop, Phase::Turn, Phase::River];
+
+fn phase_index(phase: Phase) -> usize {
+    PHASES.iter().position(|p| *p == phase).unwrap()
+}
+
+fn phase_name(phase: Phase) -> &'static str {
+    match phase {
+        Phase::PreFlop => "pre_flop",
+        Phase::Flop => "flop",
+        Phase::Turn => "turn",
+        Phase::River => "river",
+    }
+}
+
+/// Earth mover's distance between two histograms of the same bins with the same total,
+/// in units of the bin width.
+pub fn earth_movers_distance(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "Histograms must have the same number of bins");
+    let mut carried = 0.0;
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        carried += x - y;
+        distance += carried.abs();
+    }
+    distance
+}
+
+/// Centroids of given number of clusters of the points, found by k-means with k-means++ initialization.
+/// Centroids are means of their points. A centroid without points stays where it was.
+pub fn k_means<D, R>(points: &[Vec<f64>], k: usize, iterations: usize, distance: D, rng: &mut R) -> Vec<Vec<f64>>
+where
+    D: Fn(&[f64], &[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    assert!(k > 0 && k <= points.len(), "The number of clusters must be from 1 to the number of points");
+    let mut centroids = vec![points[rng.gen_range(0 .. points.len())].clone()];
+    while centroids.len() < k {
+        let weights: Vec<f64> = points.iter()
+            .map(|p| {
+                let d = centroids.iter().map(|c| distance(p, c)).fold(f64::INFINITY, f64::min);
+                d * d
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total == 0.0 {
+            // fewer distinct points than clusters
+            centroids.push(points[rng.gen_range(0 .. points.len())].clone());
+            continue;
+        }
+        let mut target = rng.gen::<f64>() * total;
+        let chosen = weights.iter().position(|w| { target -= w; target < 0.0 }).unwrap_or(points.len() - 1);
+        centroids.push(points[chosen].clone());
+    }
+
+    for _ in 0 .. iterations {
+        let mut sums = vec![vec![0.0; points[0].len()]; k];
+        let mut counts = vec![0; k];
+        for p in points {
+            let i = nearest(&centroids, p, &distance);
+            counts[i] += 1;
+            for (s, v) in sums[i].iter_mut().zip(p.iter()) {
+                *s += v;
+            }
+        }
+        let mut moved = false;
+        for ((centroid, sum), count) in centroids.iter_mut().zip(sums).zip(counts) {
+            if count == 0 {
+                continue;
+            }
+            let mean: Vec<f64> = sum.iter().map(|s| s / count as f64).collect();
+            if mean != *centroid {
+                *centroid = mean;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+    centroids
+}
+
+/// Index of the centroid nearest to the point.
+fn nearest<D: Fn(&[f64], &[f64]) -> f64>(centroids: &[Vec<f64>], point: &[f64], distance: &D) -> usize {
+    centroids.iter().enumerate()
+        .map(|(i, c)| (i, distance(point, c)))
+        .fold((0, f64::INFINITY), |best, (i, d)| if d < best.1 { (i, d) } else { best })
+        .0
+}
+
+fn mean_of_histogram(histogram: &[f64]) -> f64 {
+    histogram.iter().enumerate().map(|(i, v)| i as f64 * v).sum()
+}
+
+/// Histogram of heads-up equities of a hand class against every hand class, weighted by their combos.
+pub fn pre_flop_equity_histogram(class: HandClass, table: &PreFlopEquityTable, bin_count: usize) -> Vec<f64> {
+    let mut histogram = vec![0.0; bin_count];
+    let mut total = 0.0;
+    for opponent in HandClass::all() {
+        let combos = opponent.starting_hands().len() as f64;
+        let equity = table.class_heads_up(class, opponent);
+        histogram[bin_of(equity, bin_count)] += combos;
+        total += combos;
+    }
+    histogram.iter().map(|h| h / total).collect()
+}
+
+fn bin_of(strength: f64, bin_count: usize) -> usize {
+    ((strength * bin_count as f64) as usize).min(bin_count - 1)
+}
+
+/// Histogram of hand strengths on the river against every opponent hand, over runouts to the river
+/// sampled with given seed. The same arguments give the same histogram. Board must have 3 or more cards.
+pub fn river_strength_histogram(hand: StartingHand, board: &Board, bin_count: usize, runouts: usize, seed: u64) -> Vec<f64> {
+    HandStrength::sample(hand, board, runouts, &mut StdRng::seed_from_u64(seed)).histogram(bin_count)
+}
+
+/// Settings to build a card abstraction.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AbstractionConfig {
+    /// Number of buckets on each phase from pre-flop to river.
+    pub bucket_counts: [usize; 4],
+    /// Number of bins of strength histograms.
+    pub bin_count: usize,
+    /// Number of runouts sampled for a histogram on flop and turn.
+    pub runouts: usize,
+    /// Number of hands and boards sampled to find buckets on each phase after flop.
+    pub samples: usize,
+    /// Maximum number of k-means iterations.
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+impl Default for AbstractionConfig {
+    fn default() -> Self {
+        Self { bucket_counts: [10, 50, 50, 50], bin_count: 10, runouts: 16, samples: 2000, iterations: 50, seed: 0 }
+    }
+}
+
+/// Buckets of hands on each phase, found by k-means clustering of strength histograms with earth mover's distance.
+/// Buckets are numbered in order of the mean strength of their histograms, so bucket 0 is the weakest.
+///
+/// Pre-flop buckets are looked up by hand class. Buckets after flop are the nearest centroids
+/// to the river strength histograms of hands.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CardAbstraction {
+    bin_count: usize,
+    runouts: usize,
+    seed: u64,
+    /// Bucket of each hand class in order of HandClass::index().
+    pre_flop_buckets: Vec<usize>,
+    /// Centroids on flop, turn and river.
+    centroids: [Vec<Vec<f64>>; 3],
+}
+
+impl CardAbstraction {
+    pub fn build(config: &AbstractionConfig, table: &PreFlopEquityTable) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let bins = config.bin_count;
+
+        let classes = HandClass::all();
+        let pre_flop_histograms: Vec<Vec<f64>> = classes.iter().map(|c| pre_flop_equity_histogram(*c, table, bins)).collect();
+        let pre_flop_centroids = sorted_by_strength(
+            k_means(&pre_flop_histograms, config.bucket_counts[0], config.iterations, earth_movers_distance, &mut rng)
+        );
+        let pre_flop_buckets = pre_flop_histograms.iter()
+            .map(|h| nearest(&pre_flop_centroids, h, &earth_movers_distance))
+            .collect();
+
+        let mut centroids = [Vec::new(), Vec::new(), Vec::new()];
+        for (i, phase) in PHASES[1 ..].iter().enumerate() {
+            let histograms: Vec<Vec<f64>> = (0 .. config.samples)
+                .map(|n| {
+                    let mut board = Board::new_with_rng(&mut rng);
+                    let hand = board.deal_starting_hands(1)[0];
+                    board.deal_cards_until(*phase);
+                    river_strength_histogram(hand, &board, bins, config.runouts, config.seed.wrapping_add(n as u64))
+                })
+                .collect();
+            let k = config.bucket_counts[i + 1];
+            centroids[i] = sorted_by_strength(k_means(&histograms, k, config.iterations, earth_movers_distance, &mut rng));
+        }
+        Self { bin_count: bins, runouts: config.runouts, seed: config.seed, pre_flop_buckets, centroids }
+    }
+
+    pub fn bucket_count(&self, phase: Phase) -> usize {
+        match phase {
+            Phase::PreFlop => self.pre_flop_buckets.iter().max().map_or(0, |b| b + 1),
+            _ => self.centroids[phase_index(phase) - 1].len(),
+        }
+    }
+
+    /// Bucket of a hand on the current phase of the board.
+    pub fn bucket(&self, hand: StartingHand, board: &Board) -> usize {
+        match board.current_phase() {
+            Phase::PreFlop => self.pre_flop_buckets[hand.class().index()],
+            phase => {
+                let histogram = river_strength_histogram(hand, board, self.bin_count, self.runouts, self.seed);
+                nearest(&self.centroids[phase_index(phase) - 1], &histogram, &earth_movers_distance)
+            },
+        }
+    }
+
+    /// Writes the abstraction in CSV. Rows of pre-flop buckets are hand classes and their buckets,
+    /// and rows of the other phases are buckets and their centroid histograms.
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "version,{}", ABSTRACTION_VERSION)?;
+        writeln!(writer, "bins,{}", self.bin_count)?;
+        writeln!(writer, "runouts,{}", self.runouts)?;
+        writeln!(writer, "seed,{}", self.seed)?;
+        for class in HandClass::all() {
+            writeln!(writer, "{},{},{}", phase_name(Phase::PreFlop), class.summary(), self.pre_flop_buckets[class.index()])?;
+        }
+        for (phase, centroids) in PHASES[1 ..].iter().zip(self.centroids.iter()) {
+            for (bucket, centroid) in centroids.iter().enumerate() {
+                let values: Vec<String> = centroid.iter().map(|v| format!("{:.6}", v)).collect();
+                writeln!(writer, "{},{},{}", phase_name(*phase), bucket, values.join(","))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads an abstraction written by save().
+    pub fn load<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = reader.lines();
+        let version = lines.next().unwrap_or_else(|| Err(invalid(String::from("Unexpected end of file"))))?;
+        if version != format!("version,{}", ABSTRACTION_VERSION) {
+            return Err(invalid(format!("Unsupported version: {}", version)));
+        }
+        let mut header = |name: &str| -> io::Result<u64> {
+            let line = lines.next().unwrap_or_else(|| Err(invalid(String::from("Unexpected end of file"))))?;
+            line.strip_prefix(name)
+                .and_then(|l| l.strip_prefix(','))
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| invalid(format!("Invalid {}: {}", name, line)))
+        };
+        let bin_count = header("bins")? as usize;
+        let runouts = header("runouts")? as usize;
+        let seed = header("seed")?;
+
+        let mut pre_flop_buckets = vec![None; HandClass::COUNT];
+        let mut centroids = [Vec::new(), Vec::new(), Vec::new()];
+        for line in lines {
+            let line = line?;
+            let columns: Vec<&str> = line.split(',').collect();
+            let phase = PHASES.iter().find(|p| phase_name(**p) == columns[0])
+                .ok_or_else(|| invalid(format!("Invalid phase: {}", line)))?;
+            if *phase == Phase::PreFlop {
+                let class = columns.get(1).and_then(|c| HandClass::from_summary(c));
+                let bucket = columns.get(2).and_then(|b| b.parse::<usize>().ok());
+                match (class, bucket, columns.len()) {
+                    (Some(c), Some(b), 3) => pre_flop_buckets[c.index()] = Some(b),
+                    _ => return Err(invalid(format!("Invalid pre-flop bucket: {}", line))),
+                }
+                continue;
+            }
+            let phase_centroids = &mut centroids[phase_index(*phase) - 1];
+            if columns.len() != 2 + bin_count || columns[1] != phase_centroids.len().to_string() {
+                return Err(invalid(format!("Invalid centroid: {}", line)));
+            }
+            let mut values = Vec::with_capacity(bin_count);
+            for c in columns[2 ..].iter() {
+                values.push(c.parse::<f64>().map_err(|_| invalid(format!("Invalid value: {}", c)))?);
+            }
+            phase_centroids.push(values);
+        }
+        let pre_flop_buckets = pre_flop_buckets.into_iter().collect::<Option<Vec<usize>>>()
+            .ok_or_else(|| invalid(String::from("Some hands are missing")))?;
+        if centroids.iter().any(|c| c.is_empty()) {
+            return Err(invalid(String::from("Some phases have no buckets")));
+        }
+        Ok(Self { bin_count, runouts, seed, pre_flop_buckets, centroids })
+    }
+}
+
+fn sorted_by_strength(mut centroids: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    centroids.sort_by(|a, b| mean_of_histogram(a).partial_cmp(&mean_of_histogram(b)).unwrap());
+    centroids
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+    use texas_holdem::equity::MAX_OPPONENTS;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    /// Heads-up equities decided by the grid row of the class only, so that rows make clusters.
+    fn table() -> PreFlopEquityTable {
+        let heads_up = (0 .. HandClass::COUNT * HandClass::COUNT)
+            .map(|i| {
+                let (row, opponent_row) = (i / HandClass::COUNT / 13, i % HandClass::COUNT / 13);
+                0.5 + (opponent_row as f64 - row as f64) / 26.0
+            })
+            .collect();
+        PreFlopEquityTable::new(1, vec![[0.5; MAX_OPPONENTS]; HandClass::COUNT], heads_up)
+    }
+
+    fn config() -> AbstractionConfig {
+        AbstractionConfig { bucket_counts: [3, 2, 2, 3], bin_count: 5, runouts: 2, samples: 6, iterations: 10, seed: 7 }
+    }
+
+    #[test]
+    fn earth_movers_distance_counts_moves_between_bins() {
+        assert_eq!(0.0, earth_movers_distance(&[0.5, 0.5], &[0.5, 0.5]));
+        assert_eq!(2.0, earth_movers_distance(&[1.0, 0.0, 0.0], &[0.0, 0.0, 1.0]));
+        assert_eq!(1.0, earth_movers_distance(&[0.5, 0.0, 0.5], &[0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn k_means_separates_clusters() {
+        let points = vec![vec![0.0], vec![0.1], vec![0.2], vec![5.0], vec![5.2]];
+        let distance = |a: &[f64], b: &[f64]| (a[0] - b[0]).abs();
+        let mut centroids = k_means(&points, 2, 10, distance, &mut StdRng::seed_from_u64(1));
+        centroids.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+        assert!((centroids[0][0] - 0.1).abs() < 1e-9);
+        assert!((centroids[1][0] - 5.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn river_histogram_has_a_single_strength() {
+        let board = Board::new_with_cards(&[
+            card(Suit::Heart, 13), card(Suit::Heart, 9), card(Suit::Club, 4), card(Suit::Spade, 2), card(Suit::Heart, 3),
+        ]);
+        let nuts = river_strength_histogram(StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 12)), &board, 4, 10, 0);
+        assert_eq!(vec![0.0, 0.0, 0.0, 1.0], nuts);
+    }
+
+    #[test]
+    fn abstraction_buckets_hands_in_order_of_strength() {
+        let abstraction = CardAbstraction::build(&config(), &table());
+        assert_eq!(3, abstraction.bucket_count(Phase::PreFlop));
+        assert_eq!(3, abstraction.bucket_count(Phase::River));
+        let aces = StartingHand::new(card(Suit::Heart, 1), card(Suit::Spade, 1));
+        let deuces = StartingHand::new(card(Suit::Heart, 2), card(Suit::Spade, 2));
+        assert_eq!(2, abstraction.bucket(aces, &Board::new()));
+        assert_eq!(0, abstraction.bucket(deuces, &Board::new()));
+        let board = Board::new_with_cards(&[
+            card(Suit::Heart, 13), card(Suit::Heart, 9), card(Suit::Club, 4), card(Suit::Spade, 2), card(Suit::Heart, 3),
+        ]);
+        let nuts = StartingHand::new(card(Suit::Heart, 1), card(Suit::Heart, 12));
+        let air = StartingHand::new(card(Suit::Club, 7), card(Suit::Diamond, 6));
+        assert!(abstraction.bucket(nuts, &board) > abstraction.bucket(air, &board));
+
+        let mut buffer = Vec::new();
+        abstraction.save(&mut buffer).unwrap();
+        let loaded = CardAbstraction::load(&buffer[..]).unwrap();
+        assert_eq!(abstraction.pre_flop_buckets, loaded.pre_flop_buckets);
+        assert_eq!(abstraction.bucket(nuts, &board), loaded.bucket(nuts, &board));
+    }
+
+    #[test]
+    fn broken_file_is_not_loaded() {
+        let text = "version,1\nbins,2\nruns,1\n";
+        assert_eq!(io::ErrorKind::InvalidData, CardAbstraction::load(text.as_bytes()).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,301 @@
+use texas_holdem::card::HandClass;
+use texas_holdem::equity::PreFlopEquityTable;
+use texas_holdem::position::Position;
+use texas_holdem::range::{ COMBO_COUNT, Range };
+
+/// Table of a push/fold game before flop. Amounts are in big blinds.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PushFoldConfig {
+    /// Effective stack of every player before posting the blinds and the ante.
+    pub stack: f64,
+    /// Ante posted by every player.
+    pub ante: f64,
+    pub num_of_players: usize,
+}
+
+impl PushFoldConfig {
+    fn posted(&self, position: Position) -> f64 {
+        let blind = match position {
+            Position::SmallBlind => 0.5,
+            Position::BigBlind => 1.0,
+            _ => 0.0,
+        };
+        blind + self.ante
+    }
+}
+
+/// Push and call frequencies of the 169 hand classes at an approximate equilibrium of a push/fold game.
+///
+/// The first player to enter the pot goes all-in, the players after the pusher call or fold in turn,
+/// and the players after a caller fold. Equities are heads-up equities of hand classes,
+/// and card removal between hands is ignored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PushFoldChart {
+    config: PushFoldConfig,
+    positions: Vec<Position>,
+    /// Indexed by seat of the pusher, and then by HandClass::index().
+    push: Vec<Vec<f64>>,
+    /// Indexed by seat of the pusher, seat of the caller, and then by HandClass::index().
+    call: Vec<Vec<Vec<f64>>>,
+    max_regret: f64,
+}
+
+/// Share of a class in all combos.
+fn class_weights() -> Vec<f64> {
+    HandClass::all().iter().map(|c| c.combo_count() as f64 / COMBO_COUNT as f64).collect()
+}
+
+impl PushFoldChart {
+    /// Solves the game by fictitious play: in each iteration, every player takes the best response
+    /// to the average strategies of the others, and it is averaged into the player's strategy.
+    pub fn solve(config: &PushFoldConfig, table: &PreFlopEquityTable, iterations: usize) -> Self {
+        let positions = Position::of_table(config.num_of_players);
+        assert!(config.ante >= 0.0, "Ante must not be negative");
+        assert!(config.stack > 1.0 + config.ante, "Stack must be larger than the big blind and the ante");
+        let n = positions.len();
+        let mut chart = Self {
+            config: config.clone(),
+            positions,
+            push: vec![vec![1.0; HandClass::COUNT]; n],
+            call: vec![vec![vec![1.0; HandClass::COUNT]; n]; n],
+            max_regret: f64::INFINITY,
+        };
+        let equities: Vec<f64> = HandClass::all().iter()
+            .flat_map(|a| HandClass::all().into_iter().map(move |b| table.class_heads_up(*a, b)))
+            .collect();
+        for t in 1 ..= iterations {
+            let (push, call, max_regret) = chart.best_responses(&equities);
+            let step = 1.0 / (t + 1) as f64;
+            average_into(&mut chart.push, &push, step);
+            for (c, b) in chart.call.iter_mut().zip(call.iter()) {
+                average_into(c, b, step);
+            }
+            chart.max_regret = max_regret;
+        }
+        chart
+    }
+
+    /// Best responses to the current strategies, and the largest gain of a player deviating from them in big blinds.
+    #[allow(clippy::type_complexity)]
+    fn best_responses(&self, equities: &[f64]) -> (Vec<Vec<f64>>, Vec<Vec<Vec<f64>>>, f64) {
+        let n = self.positions.len();
+        let weights = class_weights();
+        let equity = |a: usize, b: usize| equities[a * HandClass::COUNT + b];
+        let mut max_regret: f64 = 0.0;
+        let mut push = self.push.clone();
+        let mut call = self.call.clone();
+
+        // the big blind is never folded to with a decision
+        for pusher in 0 .. n - 1 {
+            let fold_value = -self.posted(pusher);
+            let mut regret = 0.0;
+            for h in 0 .. HandClass::COUNT {
+                let mut no_call = 1.0;
+                let mut value = 0.0;
+                for caller in pusher + 1 .. n {
+                    let pot = self.pot(pusher, caller);
+                    let mut call_share = 0.0;
+                    let mut won = 0.0;
+                    for (c, (w, f)) in weights.iter().zip(self.call[pusher][caller].iter()).enumerate() {
+                        call_share += w * f;
+                        won += w * f * equity(h, c) * pot;
+                    }
+                    value += no_call * (won - call_share * self.config.stack);
+                    no_call *= 1.0 - call_share;
+                }
+                value += no_call * self.dead_money(pusher);
+                let current = self.push[pusher][h] * value + (1.0 - self.push[pusher][h]) * fold_value;
+                regret += weights[h] * (value.max(fold_value) - current);
+                push[pusher][h] = if value > fold_value { 1.0 } else { 0.0 };
+            }
+            max_regret = max_regret.max(regret);
+
+            let pushed: Vec<f64> = weights.iter().zip(self.push[pusher].iter()).map(|(w, f)| w * f).collect();
+            let push_share: f64 = pushed.iter().sum();
+            if push_share == 0.0 {
+                continue;
+            }
+            for (caller, best_response) in call[pusher].iter_mut().enumerate().skip(pusher + 1) {
+                let pot = self.pot(pusher, caller);
+                let fold_value = -self.posted(caller);
+                let mut regret = 0.0;
+                for (c, (w, frequency)) in weights.iter().zip(self.call[pusher][caller].iter()).enumerate() {
+                    let won: f64 = pushed.iter().enumerate().map(|(h, p)| p * equity(c, h)).sum::<f64>() / push_share * pot;
+                    let value = won - self.config.stack;
+                    let current = frequency * value + (1.0 - frequency) * fold_value;
+                    regret += w * (value.max(fold_value) - current);
+                    best_response[c] = if value > fold_value { 1.0 } else { 0.0 };
+                }
+                max_regret = max_regret.max(regret);
+            }
+        }
+        (push, call, max_regret)
+    }
+
+    fn posted(&self, seat: usize) -> f64 {
+        self.config.posted(self.positions[seat])
+    }
+
+    /// Chips posted by players other than the seat.
+    fn dead_money(&self, seat: usize) -> f64 {
+        (0 .. self.positions.len()).filter(|s| *s != seat).map(|s| self.posted(s)).sum()
+    }
+
+    /// The pot when the caller calls the pusher.
+    fn pot(&self, pusher: usize, caller: usize) -> f64 {
+        self.dead_money(pusher) - self.posted(caller) + 2.0 * self.config.stack
+    }
+
+    fn seat_of(&self, position: Position) -> usize {
+        self.positions.iter().position(|p| *p == position)
+            .unwrap_or_else(|| panic!("{} is not on the table", position.name()))
+    }
+
+    pub fn config(&self) -> &PushFoldConfig {
+        &self.config
+    }
+
+    /// Positions on the table in order of acting.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Positions which can push when all players before them fold. All but the big blind.
+    pub fn pushers(&self) -> &[Position] {
+        &self.positions[.. self.positions.len() - 1]
+    }
+
+    /// Positions which can call a push of the pusher.
+    pub fn callers(&self, pusher: Position) -> &[Position] {
+        &self.positions[self.seat_of(pusher) + 1 ..]
+    }
+
+    /// Frequency of going all-in with the class when all players before the position fold.
+    pub fn push_frequency(&self, position: Position, class: HandClass) -> f64 {
+        self.push[self.seat_of(position)][class.index()]
+    }
+
+    /// Frequency of calling the push of the pusher with the class when players in between fold.
+    pub fn call_frequency(&self, pusher: Position, caller: Position, class: HandClass) -> f64 {
+        let (pusher, caller) = (self.seat_of(pusher), self.seat_of(caller));
+        assert!(pusher < caller, "The caller must act after the pusher");
+        self.call[pusher][caller][class.index()]
+    }
+
+    pub fn push_range(&self, position: Position) -> Range {
+        to_range(|c| self.push_frequency(position, c))
+    }
+
+    pub fn call_range(&self, pusher: Position, caller: Position) -> Range {
+        to_range(|c| self.call_frequency(pusher, caller, c))
+    }
+
+    /// The largest gain in big blinds a player could get by deviating from the chart in the last iteration.
+    /// It approaches 0 as the chart converges.
+    pub fn max_regret(&self) -> f64 {
+        self.max_regret
+    }
+}
+
+fn average_into(average: &mut [Vec<f64>], best_response: &[Vec<f64>], step: f64) {
+    for (a, b) in average.iter_mut().zip(best_response.iter()) {
+        for (x, y) in a.iter_mut().zip(b.iter()) {
+            *x += (y - *x) * step;
+        }
+    }
+}
+
+fn to_range<F: Fn(HandClass) -> f64>(frequency: F) -> Range {
+    let mut range = Range::empty();
+    for class in HandClass::all() {
+        range.set_class_weight(class, frequency(class));
+    }
+    range
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use texas_holdem::equity::MAX_OPPONENTS;
+
+    fn class(summary: &str) -> HandClass {
+        HandClass::from_summary(summary).unwrap()
+    }
+
+    /// A class with smaller index wins 65% against one with larger index.
+    fn ordered_table() -> PreFlopEquityTable {
+        let heads_up = HandClass::all().iter()
+            .flat_map(|a| HandClass::all().into_iter().map(move |b| {
+                if a.index() < b.index() { 0.65 } else if a.index() == b.index() { 0.5 } else { 0.35 }
+            }))
+            .collect();
+        PreFlopEquityTable::new(1, vec![[0.5; MAX_OPPONENTS]; HandClass::COUNT], heads_up)
+    }
+
+    fn even_table() -> PreFlopEquityTable {
+        PreFlopEquityTable::new(1, vec![[0.5; MAX_OPPONENTS]; HandClass::COUNT], vec![0.5; HandClass::COUNT * HandClass::COUNT])
+    }
+
+    fn config(stack: f64, ante: f64, num_of_players: usize) -> PushFoldConfig {
+        PushFoldConfig { stack, ante, num_of_players }
+    }
+
+    fn combos(range: &Range) -> f64 {
+        range.combo_count()
+    }
+
+    #[test]
+    fn everything_is_pushed_and_called_with_even_equities() {
+        let chart = PushFoldChart::solve(&config(10.0, 0.0, 2), &even_table(), 50);
+        assert_eq!(COMBO_COUNT as f64, combos(&chart.push_range(Position::SmallBlind)));
+        assert_eq!(COMBO_COUNT as f64, combos(&chart.call_range(Position::SmallBlind, Position::BigBlind)));
+        assert_eq!(0.0, chart.max_regret());
+    }
+
+    #[test]
+    fn best_hand_is_always_pushed_and_called() {
+        let chart = PushFoldChart::solve(&config(20.0, 0.0, 2), &ordered_table(), 100);
+        assert_eq!(1.0, chart.push_frequency(Position::SmallBlind, class("AA")));
+        assert_eq!(1.0, chart.call_frequency(Position::SmallBlind, Position::BigBlind, class("AA")));
+        assert!(chart.push_frequency(Position::SmallBlind, class("32o")) < 0.5);
+    }
+
+    #[test]
+    fn shorter_stack_pushes_wider() {
+        let short = PushFoldChart::solve(&config(3.0, 0.0, 2), &ordered_table(), 100);
+        let deep = PushFoldChart::solve(&config(20.0, 0.0, 2), &ordered_table(), 100);
+        assert!(combos(&short.push_range(Position::SmallBlind)) > combos(&deep.push_range(Position::SmallBlind)));
+    }
+
+    #[test]
+    fn ante_widens_push_range() {
+        let without = PushFoldChart::solve(&config(15.0, 0.0, 4), &ordered_table(), 50);
+        let with = PushFoldChart::solve(&config(15.0, 0.2, 4), &ordered_table(), 50);
+        assert!(combos(&with.push_range(Position::UnderTheGun)) > combos(&without.push_range(Position::UnderTheGun)));
+    }
+
+    #[test]
+    fn regret_decreases_with_iterations() {
+        let early = PushFoldChart::solve(&config(10.0, 0.0, 3), &ordered_table(), 5);
+        let late = PushFoldChart::solve(&config(10.0, 0.0, 3), &ordered_table(), 200);
+        assert!(late.max_regret() < early.max_regret() / 5.0);
+        assert!(late.max_regret() < 0.05);
+    }
+
+    #[test]
+    fn multi_way_table_has_charts_for_each_pusher_and_caller() {
+        let chart = PushFoldChart::solve(&config(10.0, 0.0, 3), &ordered_table(), 10);
+        assert_eq!(&[Position::Button, Position::SmallBlind], chart.pushers());
+        assert_eq!(&[Position::SmallBlind, Position::BigBlind], chart.callers(Position::Button));
+        // earlier positions push tighter as more players are left behind
+        assert!(combos(&chart.push_range(Position::Button)) <= combos(&chart.push_range(Position::SmallBlind)));
+    }
+
+    #[test]
+    #[should_panic(expected = "The caller must act after the pusher")]
+    fn caller_before_pusher_is_invalid() {
+        let chart = PushFoldChart::solve(&config(10.0, 0.0, 3), &even_table(), 1);
+        chart.call_frequency(Position::SmallBlind, Position::Button, class("AA"));
+    }
+}
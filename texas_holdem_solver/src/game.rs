@@ -0,0 +1,260 @@
+use crate::profile::StrategyProfile;
+use crate::tree::{ ActionNode, BettingTree, Node, StreetConfig };
+use texas_holdem::Phase;
+use texas_holdem::card::{ Board, HandValue, StartingHand, TotalHand };
+use texas_holdem::equity::PreFlopEquityTable;
+use texas_holdem::range::Range;
+
+/// A betting tree played by two players with hands from their ranges.
+/// Payoffs are net chips from the start of the hand, so the game is zero-sum.
+#[derive(Debug, Clone)]
+pub struct Game {
+    tree: BettingTree,
+    hands: [Vec<StartingHand>; 2],
+    weights: [Vec<f64>; 2],
+    /// Share of the pot player 0 wins at showdown, indexed by hand of player 0 * hands of player 1 + hand of player 1.
+    /// None if the hands share a card.
+    equities: Vec<Option<f64>>,
+    /// Sum of weights of all pairs of hands not sharing a card.
+    total_weight: f64,
+}
+
+impl Game {
+    /// `equity` returns the share of the pot the first hand wins against the second at showdown.
+    pub fn new<F: Fn(StartingHand, StartingHand) -> f64>(tree: BettingTree, ranges: [&Range; 2], equity: F) -> Self {
+        let range_hands = |r: &Range| -> (Vec<StartingHand>, Vec<f64>) { r.hands().into_iter().unzip() };
+        let (hands0, weights0) = range_hands(ranges[0]);
+        let (hands1, weights1) = range_hands(ranges[1]);
+        let mut equities = Vec::with_capacity(hands0.len() * hands1.len());
+        let mut total_weight = 0.0;
+        for (a, wa) in hands0.iter().zip(weights0.iter()) {
+            for (b, wb) in hands1.iter().zip(weights1.iter()) {
+                if a.both_cards().iter().any(|c| b.both_cards().contains(c)) {
+                    equities.push(None);
+                } else {
+                    equities.push(Some(equity(*a, *b)));
+                    total_weight += wa * wb;
+                }
+            }
+        }
+        assert!(total_weight > 0.0, "Ranges must have hands not sharing cards");
+        Self { tree, hands: [hands0, hands1], weights: [weights0, weights1], equities, total_weight }
+    }
+
+    /// A betting round on the river of given board. Hands containing board cards are removed from the ranges.
+    pub fn river(board: &Board, ranges: [&Range; 2], config: &StreetConfig) -> Self {
+        assert_eq!(Phase::River, board.current_phase(), "River needs a board with 5 cards");
+        let cards = board.cards();
+        let ranges = [&ranges[0].without_cards(&cards), &ranges[1].without_cards(&cards)];
+        let value_of = |h: StartingHand| -> HandValue {
+            TotalHand::new_from_starting_hand_and_board(h, board)
+                .find_best_five_hand()
+                .expect("best five hand not found with 7 cards")
+                .value()
+        };
+        // evaluate each hand once instead of each pair
+        let mut values = vec![None; texas_holdem::range::COMBO_COUNT];
+        for (h, _) in ranges[0].hands().into_iter().chain(ranges[1].hands()) {
+            values[h.index()] = Some(value_of(h));
+        }
+        Self::new(BettingTree::single_street(config), ranges, |a, b| {
+            match values[a.index()].cmp(&values[b.index()]) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            }
+        })
+    }
+
+    /// Push or fold before flop with given stack in big blinds. Player 0 is the small blind.
+    /// Equities are the heads-up equities of hand classes in the table.
+    pub fn push_fold(stack: f64, ranges: [&Range; 2], table: &PreFlopEquityTable) -> Self {
+        Self::new(BettingTree::push_fold(stack), ranges, |a, b| table.heads_up(a, b))
+    }
+
+    pub fn tree(&self) -> &BettingTree {
+        &self.tree
+    }
+
+    /// Hands of the player with positive weights.
+    pub fn hands(&self, player: usize) -> &[StartingHand] {
+        &self.hands[player]
+    }
+
+    pub fn weights(&self, player: usize) -> &[f64] {
+        &self.weights[player]
+    }
+
+    /// Values of each hand of the player at a terminal node, weighted by reaches of opponent hands.
+    pub(crate) fn terminal_values(&self, node: &Node, player: usize, opponent_reach: &[f64]) -> Vec<f64> {
+        let n1 = self.hands[1].len();
+        let pair = |i: usize, j: usize| -> Option<f64> {
+            if player == 0 {
+                self.equities[i * n1 + j]
+            } else {
+                self.equities[j * n1 + i].map(|e| 1.0 - e)
+            }
+        };
+        (0 .. self.hands[player].len())
+            .map(|i| {
+                let compatible = opponent_reach.iter().enumerate().filter_map(|(j, r)| pair(i, j).map(|e| (e, *r)));
+                match node {
+                    Node::Fold { player: folder, invested } => {
+                        let payoff = if *folder == player { -invested[player] } else { invested[*folder] };
+                        payoff * compatible.map(|(_, r)| r).sum::<f64>()
+                    },
+                    Node::Showdown { invested } => {
+                        let pot = invested[0] + invested[1];
+                        compatible.map(|(e, r)| r * (e * pot - invested[player])).sum()
+                    },
+                    Node::Action(_) => panic!("Not a terminal node"),
+                }
+            })
+            .collect()
+    }
+
+    /// Values of each hand of the player when the opponent plays the profile,
+    /// and the player plays the profile too or the best response to it.
+    fn values_against(&self, node: &Node, player: usize, opponent_reach: &[f64], profile: &StrategyProfile, best_response: bool) -> Vec<f64> {
+        let n = match node {
+            Node::Action(n) => n,
+            _ => return self.terminal_values(node, player, opponent_reach),
+        };
+        if n.player == player {
+            let child_values: Vec<Vec<f64>> = n.children.iter()
+                .map(|c| self.values_against(c, player, opponent_reach, profile, best_response))
+                .collect();
+            (0 .. self.hands[player].len())
+                .map(|i| {
+                    let strategy = profile.strategy(n.id, i);
+                    if best_response {
+                        child_values.iter().map(|v| v[i]).fold(f64::NEG_INFINITY, f64::max)
+                    } else {
+                        child_values.iter().zip(strategy.iter()).map(|(v, s)| v[i] * s).sum()
+                    }
+                })
+                .collect()
+        } else {
+            let mut values = vec![0.0; self.hands[player].len()];
+            for (a, child) in n.children.iter().enumerate() {
+                let reach: Vec<f64> = opponent_reach.iter().enumerate()
+                    .map(|(j, r)| r * profile.strategy(n.id, j)[a])
+                    .collect();
+                for (v, c) in values.iter_mut().zip(self.values_against(child, player, &reach, profile, best_response)) {
+                    *v += c;
+                }
+            }
+            values
+        }
+    }
+
+    fn expected_value(&self, player: usize, profile: &StrategyProfile, best_response: bool) -> f64 {
+        let values = self.values_against(self.tree.root(), player, &self.weights[1 - player], profile, best_response);
+        let total: f64 = values.iter().zip(self.weights[player].iter()).map(|(v, w)| v * w).sum();
+        total / self.total_weight
+    }
+
+    /// Expected net chips of both players when they play the profile.
+    pub fn values(&self, profile: &StrategyProfile) -> [f64; 2] {
+        [self.expected_value(0, profile, false), self.expected_value(1, profile, false)]
+    }
+
+    /// Expected net chips of the player playing the best response to the opponent's strategy in the profile.
+    pub fn best_response_value(&self, profile: &StrategyProfile, player: usize) -> f64 {
+        self.expected_value(player, profile, true)
+    }
+
+    /// Average chips the best responses gain against the profile. It is 0 at a Nash equilibrium.
+    pub fn exploitability(&self, profile: &StrategyProfile) -> f64 {
+        (self.best_response_value(profile, 0) + self.best_response_value(profile, 1)) / 2.0
+    }
+
+    /// A profile whose strategy at each action node and hand is given by `f`.
+    /// `f` returns frequencies of the actions of the node, which are normalized to sum to 1.
+    pub fn profile_from_fn<F: Fn(&ActionNode, StartingHand) -> Vec<f64>>(&self, f: F) -> StrategyProfile {
+        let strategies = self.tree.action_nodes().iter()
+            .map(|n| {
+                self.hands[n.player].iter()
+                    .flat_map(|h| {
+                        let frequencies = f(n, *h);
+                        assert_eq!(n.actions.len(), frequencies.len(), "Frequencies must be given for each action");
+                        normalize(&frequencies)
+                    })
+                    .collect()
+            })
+            .collect();
+        StrategyProfile::new(self, strategies)
+    }
+
+    /// A profile taking every action with the same frequency.
+    pub fn uniform_profile(&self) -> StrategyProfile {
+        self.profile_from_fn(|n, _| vec![1.0; n.actions.len()])
+    }
+}
+
+/// Frequencies scaled to sum to 1, or uniform ones if they sum to 0.
+pub(crate) fn normalize(frequencies: &[f64]) -> Vec<f64> {
+    let sum: f64 = frequencies.iter().sum();
+    if sum > 0.0 {
+        frequencies.iter().map(|f| f / sum).collect()
+    } else {
+        vec![1.0 / frequencies.len() as f64; frequencies.len()]
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::Action;
+
+    fn push_fold_game(equity: f64) -> Game {
+        let range = Range::parse("AA,72o").unwrap();
+        Game::new(BettingTree::push_fold(10.0), [&range, &range], |_, _| equity)
+    }
+
+    #[test]
+    fn hands_sharing_cards_are_not_paired() {
+        let game = push_fold_game(0.5);
+        assert_eq!(18, game.hands(0).len());
+        // every combo of AA and 72o shares a card with 5 combos of the same class including itself
+        assert_eq!(18.0 * 18.0 - 18.0 * 5.0, game.total_weight);
+    }
+
+    #[test]
+    fn folding_loses_the_blind() {
+        let game = push_fold_game(0.5);
+        let profile = game.profile_from_fn(|n, _| if n.player == 0 { vec![1.0, 0.0] } else { vec![0.0, 1.0] });
+        assert_eq!([-0.5, 0.5], game.values(&profile));
+    }
+
+    #[test]
+    fn values_are_zero_sum() {
+        let game = push_fold_game(0.7);
+        let values = game.values(&game.uniform_profile());
+        assert!((values[0] + values[1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn best_response_to_folding_is_pushing() {
+        let game = push_fold_game(0.5);
+        let profile = game.profile_from_fn(|_, _| vec![1.0, 0.0]);
+        // player 0 takes the big blind by pushing every hand
+        assert_eq!(1.0, game.best_response_value(&profile, 0));
+    }
+
+    #[test]
+    fn river_pays_the_better_hand() {
+        use playing_card::card::{ CardRank, NonJokerCard, Suit };
+        let card = |suit, rank| NonJokerCard::new(suit, CardRank::new(rank));
+        let board = Board::new_with_cards(&[
+            card(Suit::Spade, 1), card(Suit::Diamond, 13), card(Suit::Club, 8), card(Suit::Heart, 5), card(Suit::Spade, 2),
+        ]);
+        let config = StreetConfig { pot: 10.0, stack: 20.0, bet_sizes: [Vec::new(), Vec::new()], all_in_raise: false };
+        let game = Game::river(&board, [&Range::parse("88").unwrap(), &Range::parse("55").unwrap()], &config);
+        assert_eq!(3, game.hands(0).len());
+        let values = game.values(&game.uniform_profile());
+        assert_eq!([5.0, -5.0], values);
+        assert_eq!(vec![Action::Check], game.tree().action_nodes()[0].actions);
+    }
+}
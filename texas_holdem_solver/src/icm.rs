@@ -0,0 +1,267 @@
+use texas_holdem::card::{ HandClass, StartingHand };
+use texas_holdem::equity::PreFlopEquityTable;
+use texas_holdem::position::Position;
+use texas_holdem::range::Range;
+
+/// Largest number of players whose equities are computed. The cost grows by 2 to the power of it.
+pub const MAX_PLAYERS: usize = 16;
+
+/// Prize equities of the players by the Independent Chip Model (Malmuth-Harville),
+/// in which a player finishes in the highest remaining place with probability proportional to the stack.
+///
+/// `payouts` are prizes from the first place. Places without a payout pay nothing.
+pub fn equities(stacks: &[f64], payouts: &[f64]) -> Vec<f64> {
+    let n = stacks.len();
+    assert!(n <= MAX_PLAYERS, "ICM supports up to {} players", MAX_PLAYERS);
+    assert!(stacks.iter().all(|s| *s >= 0.0), "Stacks must not be negative");
+    let total: f64 = stacks.iter().sum();
+    let mut equities = vec![0.0; n];
+    if total == 0.0 {
+        return equities;
+    }
+
+    // probability that the players in the mask took the places from the first, in any order
+    let mut probabilities = vec![0.0; 1 << n];
+    probabilities[0] = 1.0;
+    // the sum of stacks in the mask, built from the mask without its lowest player
+    let mut mask_stacks = vec![0.0; 1 << n];
+    for mask in 0usize .. 1 << n {
+        if mask > 0 {
+            let lowest = mask.trailing_zeros() as usize;
+            mask_stacks[mask] = mask_stacks[mask & (mask - 1)] + stacks[lowest];
+        }
+        let probability = probabilities[mask];
+        let place = mask.count_ones() as usize;
+        let remaining = total - mask_stacks[mask];
+        if probability == 0.0 || place >= payouts.len() || remaining <= 0.0 {
+            continue;
+        }
+        for (i, stack) in stacks.iter().enumerate() {
+            if mask & 1 << i != 0 || *stack == 0.0 {
+                continue;
+            }
+            let p = probability * stack / remaining;
+            equities[i] += p * payouts[place];
+            probabilities[mask | 1 << i] += p;
+        }
+    }
+    equities
+}
+
+/// Prize equities of pushing and folding a hand.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PushDecision {
+    pub push: f64,
+    pub fold: f64,
+}
+
+impl PushDecision {
+    pub fn should_push(&self) -> bool {
+        self.push > self.fold
+    }
+
+    /// Prize equity gained by pushing instead of folding.
+    pub fn push_gain(&self) -> f64 {
+        self.push - self.fold
+    }
+}
+
+/// A tournament hand in which the pusher goes all-in when all players before fold,
+/// and the players after the pusher call with their calling ranges in turn.
+/// Players after a caller fold.
+#[derive(Debug, PartialEq, Clone)]
+pub struct IcmSpot {
+    /// Stacks before posting the blinds and the ante, in order of acting before flop.
+    pub stacks: Vec<f64>,
+    /// Stacks of players not on this table, who are still in the tournament.
+    pub other_stacks: Vec<f64>,
+    pub payouts: Vec<f64>,
+    pub big_blind: f64,
+    pub ante: f64,
+}
+
+impl IcmSpot {
+    fn positions(&self) -> Vec<Position> {
+        Position::of_table(self.stacks.len())
+    }
+
+    /// Chips posted by the seat, which are not more than the stack.
+    fn posted(&self, seat: usize) -> f64 {
+        let blind = match self.positions()[seat] {
+            Position::SmallBlind => self.big_blind / 2.0,
+            Position::BigBlind => self.big_blind,
+            _ => 0.0,
+        };
+        (blind + self.ante).min(self.stacks[seat])
+    }
+
+    /// Equity of the seat when the hand ends with given stacks on this table.
+    fn equity_of(&self, seat: usize, stacks: &[f64]) -> f64 {
+        let mut all = stacks.to_vec();
+        all.extend_from_slice(&self.other_stacks);
+        equities(&all, &self.payouts)[seat]
+    }
+
+    /// Prize equities of the pusher with the hand. `call_ranges` are the calling ranges of the players
+    /// after the pusher in order, and their equities are taken from the table.
+    ///
+    /// When the pusher folds, the players after the pusher are assumed to fold too,
+    /// and the big blind takes all chips posted by every player.
+    pub fn push_decision(&self, pusher: usize, hand: StartingHand, call_ranges: &[Range], table: &PreFlopEquityTable) -> PushDecision {
+        let n = self.stacks.len();
+        assert!(pusher < n - 1, "The big blind cannot push");
+        assert_eq!(n - pusher - 1, call_ranges.len(), "A calling range is needed for every player after the pusher");
+
+        let mut folded: Vec<f64> = (0 .. n).map(|s| self.stacks[s] - self.posted(s)).collect();
+        folded[n - 1] += (0 .. n).map(|s| self.posted(s)).sum::<f64>();
+        let fold = self.equity_of(pusher, &folded);
+
+        let mut push = 0.0;
+        let mut no_call = 1.0;
+        for (k, range) in call_ranges.iter().enumerate() {
+            let caller = pusher + 1 + k;
+            // frequency of calling with a random hand out of the 1225 hands not sharing a card with the hand
+            let call = range.without_cards(&hand.both_cards()).combo_count() / 1225.0;
+            if call == 0.0 {
+                continue;
+            }
+            let equity = table.against_range(hand, range).expect("calling range is not empty");
+            let effective = self.stacks[pusher].min(self.stacks[caller]);
+            let dead_money: f64 = (0 .. n).filter(|s| *s != pusher && *s != caller).map(|s| self.posted(s)).sum();
+            let mut stacks: Vec<f64> = (0 .. n).map(|s| self.stacks[s] - self.posted(s)).collect();
+            stacks[pusher] = self.stacks[pusher] - effective;
+            stacks[caller] = self.stacks[caller] - effective;
+            let mut won = stacks.clone();
+            won[pusher] += 2.0 * effective + dead_money;
+            let mut lost = stacks;
+            lost[caller] += 2.0 * effective + dead_money;
+            let value = equity * self.equity_of(pusher, &won) + (1.0 - equity) * self.equity_of(pusher, &lost);
+            push += no_call * call * value;
+            no_call *= 1.0 - call;
+        }
+        let mut uncalled: Vec<f64> = (0 .. n).map(|s| self.stacks[s] - self.posted(s)).collect();
+        uncalled[pusher] = self.stacks[pusher] + (0 .. n).filter(|s| *s != pusher).map(|s| self.posted(s)).sum::<f64>();
+        push += no_call * self.equity_of(pusher, &uncalled);
+        PushDecision { push, fold }
+    }
+
+    /// Hand classes the pusher pushes profitably, with weight 1.
+    pub fn push_range(&self, pusher: usize, call_ranges: &[Range], table: &PreFlopEquityTable) -> Range {
+        let mut range = Range::empty();
+        for class in HandClass::all() {
+            // every combo of a class has the same equities in the table
+            let hand = class.starting_hands()[0];
+            if self.push_decision(pusher, hand, call_ranges, table).should_push() {
+                range.set_class_weight(class, 1.0);
+            }
+        }
+        range
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+    use texas_holdem::equity::MAX_OPPONENTS;
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-9, "expected {} but {}", expected, actual);
+    }
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    fn table(equity: f64) -> PreFlopEquityTable {
+        PreFlopEquityTable::new(
+            1,
+            vec![[0.5; MAX_OPPONENTS]; HandClass::COUNT],
+            vec![equity; HandClass::COUNT * HandClass::COUNT],
+        )
+    }
+
+    #[test]
+    fn winner_take_all_is_proportional_to_chips() {
+        let e = equities(&[1000.0, 3000.0], &[100.0]);
+        assert_close(25.0, e[0]);
+        assert_close(75.0, e[1]);
+    }
+
+    #[test]
+    fn equities_of_three_players_match_malmuth_harville() {
+        let e = equities(&[5000.0, 3000.0, 2000.0], &[50.0, 30.0, 20.0]);
+        // second place of the first player: it comes after the second or the third player wins
+        let first = 0.5 * 50.0 + (0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0) * 30.0 + (0.3 * 2.0 / 7.0 + 0.2 * 3.0 / 8.0) * 20.0;
+        assert_close(first, e[0]);
+        assert_close(100.0, e.iter().sum());
+    }
+
+    #[test]
+    fn busted_players_get_nothing() {
+        let e = equities(&[0.0, 1000.0, 1000.0], &[60.0, 40.0]);
+        assert_eq!(0.0, e[0]);
+        assert_close(50.0, e[1]);
+    }
+
+    #[test]
+    fn equal_stacks_split_prizes_evenly() {
+        let e = equities(&[1.0; 10], &[50.0, 30.0, 20.0]);
+        for v in e {
+            assert_close(10.0, v);
+        }
+    }
+
+    fn bubble() -> IcmSpot {
+        // heads-up on this table, with a short stack elsewhere on the bubble of two paid places
+        IcmSpot { stacks: vec![5000.0, 5000.0], other_stacks: vec![500.0], payouts: vec![60.0, 40.0], big_blind: 200.0, ante: 0.0 }
+    }
+
+    #[test]
+    fn pushing_against_no_callers_takes_the_blinds() {
+        let spot = bubble();
+        let hand = StartingHand::new(card(Suit::Spade, 7), card(Suit::Heart, 2));
+        let decision = spot.push_decision(0, hand, &[Range::empty()], &table(0.5));
+        assert_close(equities(&[5200.0, 4800.0, 500.0], &spot.payouts)[0], decision.push);
+        assert_close(equities(&[4900.0, 5100.0, 500.0], &spot.payouts)[0], decision.fold);
+        assert!(decision.should_push());
+    }
+
+    #[test]
+    fn big_blind_takes_all_posted_chips_when_the_pusher_folds() {
+        let spot = IcmSpot { stacks: vec![3000.0; 3], other_stacks: vec![1000.0], payouts: vec![50.0, 30.0, 20.0], big_blind: 200.0, ante: 50.0 };
+        let hand = StartingHand::new(card(Suit::Spade, 7), card(Suit::Heart, 2));
+        let decision = spot.push_decision(0, hand, &[Range::empty(), Range::empty()], &table(0.5));
+        // antes of 150 and blinds of 300 go to the big blind
+        assert_close(equities(&[2950.0, 2850.0, 3200.0, 1000.0], &spot.payouts)[0], decision.fold);
+        assert_close(equities(&[3400.0, 2850.0, 2750.0, 1000.0], &spot.payouts)[0], decision.push);
+    }
+
+    #[test]
+    fn coin_flip_for_all_chips_is_bad_on_the_bubble() {
+        let spot = bubble();
+        let hand = StartingHand::new(card(Suit::Spade, 7), card(Suit::Heart, 2));
+        // a fair flip gains chips but loses prize equity
+        let decision = spot.push_decision(0, hand, &[Range::full()], &table(0.5));
+        assert!(!decision.should_push());
+        assert!(decision.push_gain() < 0.0);
+    }
+
+    #[test]
+    fn push_range_shrinks_against_wider_calls() {
+        let spot = IcmSpot { stacks: vec![2000.0, 3000.0], other_stacks: vec![3000.0], payouts: vec![50.0, 30.0, 20.0], big_blind: 200.0, ante: 20.0 };
+        let table = table(0.5);
+        let tight = spot.push_range(0, &[Range::parse("QQ+").unwrap()], &table);
+        let wide = spot.push_range(0, &[Range::full()], &table);
+        assert_eq!(1326.0, tight.combo_count());
+        assert_eq!(0.0, wide.combo_count());
+    }
+
+    #[test]
+    #[should_panic(expected = "calling range is needed")]
+    fn calling_ranges_must_be_given_for_every_player_after_the_pusher() {
+        let hand = StartingHand::new(card(Suit::Spade, 7), card(Suit::Heart, 2));
+        bubble().push_decision(0, hand, &[], &table(0.5));
+    }
+}
@@ -0,0 +1,11 @@
+extern crate playing_card;
+extern crate rand;
+extern crate texas_holdem;
+
+pub mod abstraction;
+pub mod chart;
+pub mod game;
+pub mod icm;
+pub mod profile;
+pub mod solver;
+pub mod tree;
@@ -0,0 +1,111 @@
+use crate::game::Game;
+use std::io::{ self, Write };
+use texas_holdem::card::StartingHand;
+
+/// Frequencies of actions at each action node for each hand of the acting player.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StrategyProfile {
+    players: Vec<usize>,
+    action_counts: Vec<usize>,
+    hands: [Vec<StartingHand>; 2],
+    /// Indexed by node id, and then by hand index * number of actions + action index.
+    strategies: Vec<Vec<f64>>,
+}
+
+impl StrategyProfile {
+    pub(crate) fn new(game: &Game, strategies: Vec<Vec<f64>>) -> Self {
+        let nodes = game.tree().action_nodes();
+        Self {
+            players: nodes.iter().map(|n| n.player).collect(),
+            action_counts: nodes.iter().map(|n| n.actions.len()).collect(),
+            hands: [game.hands(0).to_vec(), game.hands(1).to_vec()],
+            strategies,
+        }
+    }
+
+    /// Frequencies of actions at the node for the hand at given index in Game::hands().
+    pub fn strategy(&self, node_id: usize, hand_index: usize) -> &[f64] {
+        let count = self.action_counts[node_id];
+        &self.strategies[node_id][hand_index * count .. (hand_index + 1) * count]
+    }
+
+    /// Frequencies of actions at the node for the hand. None if the hand is not in the range of the acting player.
+    pub fn frequencies(&self, node_id: usize, hand: StartingHand) -> Option<&[f64]> {
+        let index = self.hands[self.players[node_id]].iter().position(|h| *h == hand)?;
+        Some(self.strategy(node_id, index))
+    }
+
+    /// The profile with strategies of the player taken from another profile of the same game,
+    /// e.g. to play a heuristic strategy against an equilibrium one.
+    pub fn with_player_from(&self, player: usize, other: &Self) -> Self {
+        assert_eq!(self.action_counts, other.action_counts, "Profiles of different games cannot be combined");
+        let mut profile = self.clone();
+        for (i, p) in self.players.iter().enumerate() {
+            if *p == player {
+                profile.strategies[i] = other.strategies[i].clone();
+            }
+        }
+        profile
+    }
+
+    /// Writes a row of node, player, hand, action and frequency for each action of each hand at each node.
+    pub fn write_csv<W: Write>(&self, game: &Game, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "node,player,hand,action,frequency")?;
+        for node in game.tree().action_nodes() {
+            for (i, hand) in self.hands[node.player].iter().enumerate() {
+                let cards = hand.both_cards();
+                for (action, frequency) in node.actions.iter().zip(self.strategy(node.id, i)) {
+                    writeln!(
+                        writer, "{},{},{}{},{},{:.6}",
+                        node.history_name(), node.player, cards[0], cards[1], action, frequency
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::BettingTree;
+    use texas_holdem::range::Range;
+
+    fn game() -> Game {
+        let range = Range::parse("AA").unwrap();
+        Game::new(BettingTree::push_fold(10.0), [&range, &range], |_, _| 0.5)
+    }
+
+    #[test]
+    fn frequencies_are_normalized() {
+        let game = game();
+        let hand = game.hands(0)[0];
+        let profile = game.profile_from_fn(|_, _| vec![1.0, 3.0]);
+        assert_eq!(Some(&[0.25, 0.75][..]), profile.frequencies(0, hand));
+    }
+
+    #[test]
+    fn strategies_of_a_player_are_replaced() {
+        let game = game();
+        let folding = game.profile_from_fn(|_, _| vec![1.0, 0.0]);
+        let calling = game.profile_from_fn(|_, _| vec![0.0, 1.0]);
+        let profile = folding.with_player_from(1, &calling);
+        assert_eq!(&[1.0, 0.0], profile.strategy(0, 0));
+        assert_eq!(&[0.0, 1.0], profile.strategy(1, 0));
+    }
+
+    #[test]
+    fn csv_has_a_row_per_action_of_each_hand() {
+        let game = game();
+        let mut buffer = Vec::new();
+        game.uniform_profile().write_csv(&game, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(1 + 2 * 6 * 2, lines.len());
+        assert!(lines[1].starts_with("root,0,"));
+        assert!(lines[1].ends_with(",fold,0.500000"));
+        assert!(lines[13].starts_with("all-in,1,"));
+    }
+}
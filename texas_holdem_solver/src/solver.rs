@@ -0,0 +1,217 @@
+use crate::game::{ self, Game };
+use crate::profile::StrategyProfile;
+use crate::tree::Node;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CfrVariant {
+    /// Counterfactual regret minimization with the plain average of strategies.
+    Vanilla,
+    /// CFR+, which floors regrets at 0 and weights the average strategy by iteration.
+    Plus,
+}
+
+/// Solves a game by counterfactual regret minimization over all hands of both ranges at once.
+/// The players update their regrets alternately in each iteration.
+pub struct Solver<'a> {
+    game: &'a Game,
+    variant: CfrVariant,
+    /// Indexed by node id, and then by hand index * number of actions + action index.
+    regrets: Vec<Vec<f64>>,
+    strategy_sums: Vec<Vec<f64>>,
+    iteration: usize,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(game: &'a Game, variant: CfrVariant) -> Self {
+        let sizes: Vec<usize> = game.tree().action_nodes().iter()
+            .map(|n| game.hands(n.player).len() * n.actions.len())
+            .collect();
+        Self {
+            game,
+            variant,
+            regrets: sizes.iter().map(|s| vec![0.0; *s]).collect(),
+            strategy_sums: sizes.iter().map(|s| vec![0.0; *s]).collect(),
+            iteration: 0,
+        }
+    }
+
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    pub fn run(&mut self, iterations: usize) {
+        for _ in 0 .. iterations {
+            self.iteration += 1;
+            for player in 0 .. 2 {
+                let own = self.game.weights(player).to_vec();
+                let opponent = self.game.weights(1 - player).to_vec();
+                self.update(self.game.tree().root(), player, &own, &opponent);
+            }
+        }
+    }
+
+    /// Average strategy so far, which converges to a Nash equilibrium.
+    pub fn average_strategy(&self) -> StrategyProfile {
+        let nodes = self.game.tree().action_nodes();
+        let strategies = nodes.iter().zip(self.strategy_sums.iter())
+            .map(|(n, sums)| sums.chunks(n.actions.len()).flat_map(game::normalize).collect())
+            .collect();
+        StrategyProfile::new(self.game, strategies)
+    }
+
+    /// Exploitability of the average strategy in chips.
+    pub fn exploitability(&self) -> f64 {
+        self.game.exploitability(&self.average_strategy())
+    }
+
+    /// Strategy of each hand at the node by regret matching.
+    fn current_strategy(&self, node_id: usize, action_count: usize) -> Vec<f64> {
+        self.regrets[node_id].chunks(action_count)
+            .flat_map(|r| game::normalize(&r.iter().map(|r| r.max(0.0)).collect::<Vec<f64>>()))
+            .collect()
+    }
+
+    /// Updates regrets of the player below the node and returns counterfactual values of each hand of the player.
+    fn update(&mut self, node: &'a Node, player: usize, own_reach: &[f64], opponent_reach: &[f64]) -> Vec<f64> {
+        let n = match node {
+            Node::Action(n) => n,
+            _ => return self.game.terminal_values(node, player, opponent_reach),
+        };
+        let count = n.actions.len();
+        let strategy = self.current_strategy(n.id, count);
+        if n.player != player {
+            let mut values = vec![0.0; own_reach.len()];
+            for (a, child) in n.children.iter().enumerate() {
+                let reach: Vec<f64> = opponent_reach.iter().enumerate().map(|(j, r)| r * strategy[j * count + a]).collect();
+                for (v, c) in values.iter_mut().zip(self.update(child, player, own_reach, &reach)) {
+                    *v += c;
+                }
+            }
+            return values;
+        }
+
+        let child_values: Vec<Vec<f64>> = n.children.iter().enumerate()
+            .map(|(a, child)| {
+                let reach: Vec<f64> = own_reach.iter().enumerate().map(|(i, r)| r * strategy[i * count + a]).collect();
+                self.update(child, player, &reach, opponent_reach)
+            })
+            .collect();
+        let average_weight = match self.variant {
+            CfrVariant::Vanilla => 1.0,
+            CfrVariant::Plus => self.iteration as f64,
+        };
+        let mut values = vec![0.0; own_reach.len()];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = (0 .. count).map(|a| strategy[i * count + a] * child_values[a][i]).sum();
+            for (a, v) in child_values.iter().enumerate() {
+                let regret = &mut self.regrets[n.id][i * count + a];
+                *regret += v[i] - *value;
+                if self.variant == CfrVariant::Plus {
+                    *regret = regret.max(0.0);
+                }
+                self.strategy_sums[n.id][i * count + a] += average_weight * own_reach[i] * strategy[i * count + a];
+            }
+        }
+        values
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::StreetConfig;
+    use playing_card::card::{ CardRank, NonJokerCard, Suit };
+    use texas_holdem::card::{ Board, HandClass, StartingHand };
+    use texas_holdem::equity::{ MAX_OPPONENTS, PreFlopEquityTable };
+    use texas_holdem::range::Range;
+
+    fn card(suit: Suit, rank: u8) -> NonJokerCard {
+        NonJokerCard::new(suit, CardRank::new(rank))
+    }
+
+    /// Player 0 holds trip aces or seven high, and player 1 holds trip kings, which only beats seven high.
+    /// Player 0 can bet the pot, and player 1 can only check or call.
+    fn polarized_river() -> (Game, StartingHand, StartingHand) {
+        let board = Board::new_with_cards(&[
+            card(Suit::Spade, 1), card(Suit::Diamond, 13), card(Suit::Club, 8), card(Suit::Heart, 5), card(Suit::Spade, 2),
+        ]);
+        let aces = StartingHand::new(card(Suit::Heart, 1), card(Suit::Diamond, 1));
+        let air = StartingHand::new(card(Suit::Heart, 7), card(Suit::Heart, 6));
+        let mut range0 = Range::empty();
+        range0.set_weight(aces, 1.0);
+        range0.set_weight(air, 1.0);
+        let mut range1 = Range::empty();
+        range1.set_weight(StartingHand::new(card(Suit::Heart, 13), card(Suit::Club, 13)), 1.0);
+        let config = StreetConfig { pot: 10.0, stack: 100.0, bet_sizes: [vec![1.0], Vec::new()], all_in_raise: false };
+        (Game::river(&board, [&range0, &range1], &config), aces, air)
+    }
+
+    #[test]
+    fn polarized_river_converges_to_known_equilibrium() {
+        let (game, aces, air) = polarized_river();
+        let mut solver = Solver::new(&game, CfrVariant::Plus);
+        solver.run(2000);
+        let profile = solver.average_strategy();
+        // value hands always bet, and bluffs are half of value hands to make calling indifferent
+        assert!(profile.frequencies(0, aces).unwrap()[1] > 0.99);
+        assert!((profile.frequencies(0, air).unwrap()[1] - 0.5).abs() < 0.05);
+        // calling half the time makes bluffing indifferent
+        let call = profile.strategy(2, 0)[1];
+        assert!((call - 0.5).abs() < 0.05, "call frequency {}", call);
+        assert!(solver.exploitability() < 0.05);
+    }
+
+    #[test]
+    fn exploitability_decreases_with_iterations() {
+        let (game, _, _) = polarized_river();
+        let uniform = game.exploitability(&game.uniform_profile());
+        let mut solver = Solver::new(&game, CfrVariant::Vanilla);
+        solver.run(10);
+        let early = solver.exploitability();
+        solver.run(1000);
+        assert_eq!(1010, solver.iteration());
+        assert!(early < uniform);
+        assert!(solver.exploitability() < early);
+        assert!(solver.exploitability() < 0.1);
+    }
+
+    #[test]
+    fn heuristic_strategy_is_more_exploitable_than_solution() {
+        let (game, aces, _) = polarized_river();
+        let mut solver = Solver::new(&game, CfrVariant::Plus);
+        solver.run(500);
+        let solution = solver.average_strategy();
+        // bet only with aces and always call
+        let heuristic = game.profile_from_fn(|n, h| {
+            if n.player == 0 && n.history.is_empty() {
+                if h == aces { vec![0.0, 1.0] } else { vec![1.0, 0.0] }
+            } else {
+                vec![0.0; n.actions.len() - 1].into_iter().chain(vec![1.0]).collect()
+            }
+        });
+        assert!(game.exploitability(&heuristic) > 10.0 * game.exploitability(&solution));
+        // player 0 playing the heuristic against the solution of player 1 earns less than the best response
+        let mixed = solution.with_player_from(0, &heuristic);
+        assert!(game.values(&mixed)[0] < game.best_response_value(&solution, 0) + 1e-9);
+    }
+
+    #[test]
+    fn even_push_fold_pushes_and_calls_everything() {
+        let table = PreFlopEquityTable::new(
+            1,
+            vec![[0.5; MAX_OPPONENTS]; HandClass::COUNT],
+            vec![0.5; HandClass::COUNT * HandClass::COUNT],
+        );
+        let range = Range::parse("AA,KK,72o").unwrap();
+        let game = Game::push_fold(10.0, [&range, &range], &table);
+        let mut solver = Solver::new(&game, CfrVariant::Plus);
+        solver.run(100);
+        let profile = solver.average_strategy();
+        for i in 0 .. game.hands(0).len() {
+            assert!(profile.strategy(0, i)[1] > 0.99);
+            assert!(profile.strategy(1, i)[1] > 0.99);
+        }
+        assert!(solver.exploitability() < 1e-3);
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    /// Bet of given chips.
+    Bet(f64),
+    /// Bet or raise of all remaining chips.
+    AllIn,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fold => write!(f, "fold"),
+            Self::Check => write!(f, "check"),
+            Self::Call => write!(f, "call"),
+            Self::Bet(amount) => write!(f, "bet {}", amount),
+            Self::AllIn => write!(f, "all-in"),
+        }
+    }
+}
+
+/// A node of a betting tree of two players, where player 0 acts first.
+/// Chips put in by the players are counted from the start of the hand,
+/// so the pot before the tree is split evenly between them.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Node {
+    Action(ActionNode),
+    /// `player` folded.
+    Fold { player: usize, invested: [f64; 2] },
+    Showdown { invested: [f64; 2] },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ActionNode {
+    /// Index in BettingTree::action_nodes().
+    pub id: usize,
+    pub player: usize,
+    /// Actions taken before this node.
+    pub history: Vec<Action>,
+    pub actions: Vec<Action>,
+    pub children: Vec<Node>,
+}
+
+impl ActionNode {
+    /// Actions taken before this node separated by slashes, or "root".
+    pub fn history_name(&self) -> String {
+        if self.history.is_empty() {
+            String::from("root")
+        } else {
+            self.history.iter().map(|a| a.to_string()).collect::<Vec<String>>().join("/")
+        }
+    }
+}
+
+/// Pot and stacks of a betting round on a single street.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StreetConfig {
+    pub pot: f64,
+    /// Effective stack remaining behind.
+    pub stack: f64,
+    /// Bet sizes of each player as fractions of the pot. A player without bet sizes can only check.
+    pub bet_sizes: [Vec<f64>; 2],
+    /// Allows the player facing a bet to raise all-in.
+    pub all_in_raise: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BettingTree {
+    root: Node,
+    action_node_count: usize,
+}
+
+impl BettingTree {
+    /// Player 0 posts the small blind of 0.5 and player 1 posts the big blind of 1.
+    /// Player 0 folds or goes all-in with the stack, and player 1 folds or calls.
+    pub fn push_fold(stack: f64) -> Self {
+        assert!(stack > 1.0, "Stack must be larger than the big blind");
+        let call = Node::Action(ActionNode {
+            id: 1,
+            player: 1,
+            history: vec![Action::AllIn],
+            actions: vec![Action::Fold, Action::Call],
+            children: vec![
+                Node::Fold { player: 1, invested: [stack, 1.0] },
+                Node::Showdown { invested: [stack, stack] },
+            ],
+        });
+        let root = Node::Action(ActionNode {
+            id: 0,
+            player: 0,
+            history: Vec::new(),
+            actions: vec![Action::Fold, Action::AllIn],
+            children: vec![Node::Fold { player: 0, invested: [0.5, 1.0] }, call],
+        });
+        Self { root, action_node_count: 2 }
+    }
+
+    /// A betting round on a single street such as the river.
+    /// Each player can check or bet once, and the player facing a bet can fold, call or raise all-in if allowed.
+    pub fn single_street(config: &StreetConfig) -> Self {
+        let mut builder = Builder { config, action_node_count: 0 };
+        let half = config.pot / 2.0;
+        let root = builder.unopened(0, Vec::new(), [half, half]);
+        Self { root, action_node_count: builder.action_node_count }
+    }
+
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    /// Action nodes in order of their ids.
+    pub fn action_nodes(&self) -> Vec<&ActionNode> {
+        let mut nodes = Vec::with_capacity(self.action_node_count);
+        collect_action_nodes(&self.root, &mut nodes);
+        nodes.sort_by_key(|n| n.id);
+        nodes
+    }
+}
+
+fn collect_action_nodes<'a>(node: &'a Node, nodes: &mut Vec<&'a ActionNode>) {
+    if let Node::Action(n) = node {
+        nodes.push(n);
+        for child in n.children.iter() {
+            collect_action_nodes(child, nodes);
+        }
+    }
+}
+
+struct Builder<'a> {
+    config: &'a StreetConfig,
+    action_node_count: usize,
+}
+
+impl<'a> Builder<'a> {
+    fn next_id(&mut self) -> usize {
+        self.action_node_count += 1;
+        self.action_node_count - 1
+    }
+
+    /// A node of a player who can check or bet.
+    fn unopened(&mut self, player: usize, history: Vec<Action>, invested: [f64; 2]) -> Node {
+        let id = self.next_id();
+        let mut actions = vec![Action::Check];
+        let mut children = Vec::new();
+        let mut check_history = history.clone();
+        check_history.push(Action::Check);
+        children.push(if player == 0 {
+            self.unopened(1, check_history, invested)
+        } else {
+            Node::Showdown { invested }
+        });
+
+        let pot = invested[0] + invested[1];
+        let mut amounts: Vec<f64> = self.config.bet_sizes[player].iter()
+            .map(|s| (s * pot).min(self.config.stack))
+            .collect();
+        amounts.dedup();
+        for amount in amounts {
+            let action = if amount == self.config.stack { Action::AllIn } else { Action::Bet(amount) };
+            let mut bet_history = history.clone();
+            bet_history.push(action);
+            let mut bet_invested = invested;
+            bet_invested[player] += amount;
+            actions.push(action);
+            children.push(self.facing_bet(1 - player, bet_history, bet_invested));
+        }
+        Node::Action(ActionNode { id, player, history, actions, children })
+    }
+
+    /// A node of a player who can fold or call the bet, or raise all-in if allowed.
+    fn facing_bet(&mut self, player: usize, history: Vec<Action>, invested: [f64; 2]) -> Node {
+        let id = self.next_id();
+        let mut called = invested;
+        called[player] = invested[1 - player];
+        let mut actions = vec![Action::Fold, Action::Call];
+        let mut children = vec![
+            Node::Fold { player, invested },
+            Node::Showdown { invested: called },
+        ];
+        let all_in = self.config.pot / 2.0 + self.config.stack;
+        if self.config.all_in_raise && invested[1 - player] < all_in && !history.contains(&Action::AllIn) {
+            let mut raise_history = history.clone();
+            raise_history.push(Action::AllIn);
+            let mut raised = invested;
+            raised[player] = all_in;
+            actions.push(Action::AllIn);
+            children.push(self.facing_bet(1 - player, raise_history, raised));
+        }
+        Node::Action(ActionNode { id, player, history, actions, children })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(bet_sizes: [Vec<f64>; 2], all_in_raise: bool) -> StreetConfig {
+        StreetConfig { pot: 10.0, stack: 20.0, bet_sizes, all_in_raise }
+    }
+
+    #[test]
+    fn push_fold_tree_has_two_decisions() {
+        let tree = BettingTree::push_fold(10.0);
+        let nodes = tree.action_nodes();
+        assert_eq!(2, nodes.len());
+        assert_eq!(vec![Action::Fold, Action::AllIn], nodes[0].actions);
+        assert_eq!(Node::Showdown { invested: [10.0, 10.0] }, nodes[1].children[1]);
+    }
+
+    #[test]
+    fn street_without_bets_checks_down() {
+        let tree = BettingTree::single_street(&config([Vec::new(), Vec::new()], false));
+        let nodes = tree.action_nodes();
+        assert_eq!(2, nodes.len());
+        assert_eq!(Node::Showdown { invested: [5.0, 5.0] }, nodes[1].children[0]);
+        assert_eq!("check", nodes[1].history_name());
+    }
+
+    #[test]
+    fn bets_are_capped_by_stack() {
+        let tree = BettingTree::single_street(&config([vec![0.5, 3.0], Vec::new()], false));
+        let root = tree.action_nodes()[0];
+        assert_eq!(vec![Action::Check, Action::Bet(5.0), Action::AllIn], root.actions);
+        match &root.children[2] {
+            Node::Action(n) => assert_eq!(Node::Showdown { invested: [25.0, 25.0] }, n.children[1]),
+            _ => panic!("bet is not followed by a decision"),
+        }
+    }
+
+    #[test]
+    fn all_in_raise_is_answered_by_the_bettor() {
+        let tree = BettingTree::single_street(&config([vec![0.5], Vec::new()], true));
+        let nodes = tree.action_nodes();
+        let raised = nodes.iter().find(|n| n.history == vec![Action::Bet(5.0), Action::AllIn]).unwrap();
+        assert_eq!(0, raised.player);
+        assert_eq!(vec![Action::Fold, Action::Call], raised.actions);
+        assert_eq!(Node::Fold { player: 0, invested: [10.0, 25.0] }, raised.children[0]);
+    }
+
+    #[test]
+    fn ids_are_indices_of_action_nodes() {
+        let tree = BettingTree::single_street(&config([vec![0.5, 1.0], vec![1.0]], true));
+        for (i, n) in tree.action_nodes().iter().enumerate() {
+            assert_eq!(i, n.id);
+        }
+    }
+}